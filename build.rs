@@ -1,8 +1,38 @@
-use std::{collections::HashSet, env, path::PathBuf};
+use std::{collections::HashSet, env, fs, path::PathBuf};
 
 const MIN_VERSION: &str = "0.10";
 
+/// ZBar versions which add APIs that this crate wraps. A `zbar_<major>_<minor>` cfg flag is set for every version which the ZBar library is at least.
+const FEATURE_VERSIONS: [(u32, u32); 2] = [(0, 22), (0, 23)];
+
 fn main() {
+    if cfg!(feature = "vendored") && cfg!(feature = "dynamic-loading") {
+        panic!("The `vendored` and `dynamic-loading` features cannot be enabled together.");
+    }
+
+    let version = if cfg!(feature = "vendored") {
+        build_vendored()
+    } else if cfg!(feature = "dynamic-loading") {
+        detect_system()
+    } else {
+        link_system()
+    };
+
+    for (major, minor) in FEATURE_VERSIONS {
+        let name = format!("zbar_{}_{}", major, minor);
+
+        println!("cargo:rustc-check-cfg=cfg({})", name);
+
+        if (version.0, version.1) >= (major, minor) {
+            println!("cargo:rustc-cfg={}", name);
+        }
+    }
+
+    write_version_file(version);
+}
+
+/// Link the ZBar library of the system and find out its version.
+fn link_system() -> (u32, u32, u32) {
     if cfg!(target_os = "freebsd") {
         env_var_set_default("ZBAR_INCLUDE_DIRS", "/usr/include");
         env_var_set_default("ZBAR_LIB_DIRS", "/usr/lib");
@@ -25,6 +55,17 @@ fn main() {
         }
         println!("cargo:include={}", d.to_string_lossy());
     }
+
+    let version = detect_zbar_version(&include_dirs).unwrap_or_else(|| {
+        println!(
+            "cargo:warning=The version of ZBar cannot be detected, so ZBar {} is assumed. Set \
+             ZBAR_VERSION to the version of the linked ZBar library.",
+            MIN_VERSION
+        );
+
+        parse_version(MIN_VERSION).unwrap()
+    });
+
     println!("cargo:rerun-if-env-changed=ZBAR_LIBS");
 
     let target = env::var("TARGET").unwrap();
@@ -56,6 +97,32 @@ fn main() {
         Ok(_) => println!("cargo:rustc-link-lib=dylib=stdc++"),
         Err(_) => (),
     }
+
+    version
+}
+
+/// Find out the version of the ZBar library of the system without linking it, for loading it at runtime. The library does not have to be installed, but then `ZBAR_VERSION` has to be set.
+fn detect_system() -> (u32, u32, u32) {
+    println!("cargo:rerun-if-env-changed=ZBAR_INCLUDE_DIRS");
+    let include_dirs = env::var("ZBAR_INCLUDE_DIRS")
+        .map(|x| x.split(':').map(PathBuf::from).collect::<Vec<PathBuf>>())
+        .or_else(|_| Ok(vec![find_zbar_dir()?.join("include")]))
+        .unwrap_or_else(|_: env::VarError| {
+            pkg_config::Config::new()
+                .cargo_metadata(false)
+                .probe("zbar")
+                .map(|library| library.include_paths)
+                .unwrap_or_default()
+        });
+
+    // the bindings of an assumed version would not fit the library loaded at runtime
+    detect_zbar_version(&include_dirs).unwrap_or_else(|| {
+        panic!(
+            "The version of ZBar cannot be detected for the `dynamic-loading` feature. Set \
+             ZBAR_VERSION to the version of the ZBar library which will be loaded at runtime, \
+             such as `0.23`."
+        )
+    })
 }
 
 fn env_var_set_default(name: &str, value: &str) {
@@ -89,6 +156,67 @@ fn find_zbar_dir() -> Result<PathBuf, env::VarError> {
     env::var("ZBAR_DIR").map(PathBuf::from)
 }
 
+/// Find out the version of ZBar, from the `ZBAR_VERSION` environment variable, the header file or pkg-config, in that order.
+fn detect_zbar_version(include_dirs: &[PathBuf]) -> Option<(u32, u32, u32)> {
+    println!("cargo:rerun-if-env-changed=ZBAR_VERSION");
+    if let Ok(version) = env::var("ZBAR_VERSION") {
+        return Some(
+            parse_version(&version).unwrap_or_else(|| panic!("Invalid ZBAR_VERSION: {}", version)),
+        );
+    }
+
+    for d in include_dirs {
+        let header = d.join("zbar.h");
+
+        if let Ok(content) = fs::read_to_string(&header) {
+            println!("cargo:rerun-if-changed={}", header.to_string_lossy());
+
+            // the header does not define its version, so look for what each version introduced
+            return Some(if content.contains("ZBAR_SQCODE") {
+                (0, 23, 0)
+            } else if content.contains("zbar_image_scanner_get_config") {
+                (0, 22, 0)
+            } else {
+                parse_version(MIN_VERSION).unwrap()
+            });
+        }
+    }
+
+    pkg_config::Config::new()
+        .cargo_metadata(false)
+        .probe("zbar")
+        .ok()
+        .and_then(|library| parse_version(&library.version))
+}
+
+fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version.trim().split('.');
+
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = match parts.next() {
+        Some(patch) => patch.parse().ok()?,
+        None => 0,
+    };
+
+    Some((major, minor, patch))
+}
+
+/// Write the detected version and the feature versions for the `version` module.
+fn write_version_file(version: (u32, u32, u32)) {
+    let path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("version.rs");
+
+    let content = format!(
+        "const DETECTED_VERSION: (u32, u32, u32) = {:?};\n\nconst FEATURE_VERSIONS: [(u32, u32); \
+         {}] = {:?};\n",
+        version,
+        FEATURE_VERSIONS.len(),
+        FEATURE_VERSIONS
+    );
+
+    fs::write(path, content).unwrap();
+}
+
 fn determine_mode<T: AsRef<str>>(libdirs: &[PathBuf], libs: &[T]) -> &'static str {
     println!("cargo:rerun-if-env-changed=ZBAR_STATIC");
     let kind = env::var("ZBAR_STATIC").ok();
@@ -140,3 +268,144 @@ fn run_pkg_config() -> pkg_config::Library {
 
     pkg_config::Config::new().cargo_metadata(false).probe("zbar").unwrap()
 }
+
+/// Symbologies which can be left out of the vendored build, with their cargo features, `ENABLE_*` macros and source files.
+#[cfg(feature = "vendored")]
+const VENDORED_SYMBOLOGIES: [(&str, &str, &[&str]); 10] = [
+    ("VENDORED_EAN", "ENABLE_EAN", &["zbar/decoder/ean.c"]),
+    ("VENDORED_I25", "ENABLE_I25", &["zbar/decoder/i25.c"]),
+    ("VENDORED_DATABAR", "ENABLE_DATABAR", &["zbar/decoder/databar.c"]),
+    ("VENDORED_CODABAR", "ENABLE_CODABAR", &["zbar/decoder/codabar.c"]),
+    ("VENDORED_CODE39", "ENABLE_CODE39", &["zbar/decoder/code39.c"]),
+    ("VENDORED_CODE93", "ENABLE_CODE93", &["zbar/decoder/code93.c"]),
+    ("VENDORED_CODE128", "ENABLE_CODE128", &["zbar/decoder/code128.c"]),
+    ("VENDORED_PDF417", "ENABLE_PDF417", &["zbar/decoder/pdf417.c"]),
+    ("VENDORED_QRCODE", "ENABLE_QRCODE", &[
+        "zbar/decoder/qr_finder.c",
+        "zbar/qrcode/bch15_5.c",
+        "zbar/qrcode/binarize.c",
+        "zbar/qrcode/isaac.c",
+        "zbar/qrcode/qrdec.c",
+        "zbar/qrcode/qrdectxt.c",
+        "zbar/qrcode/rs.c",
+        "zbar/qrcode/util.c",
+    ]),
+    ("VENDORED_SQCODE", "ENABLE_SQCODE", &["zbar/decoder/sq_finder.c", "zbar/sqcode.c"]),
+];
+
+/// The sources of the image scanner, without video, window and processor support.
+#[cfg(feature = "vendored")]
+const VENDORED_CORE_SOURCES: [&str; 9] = [
+    "zbar/config.c",
+    "zbar/convert.c",
+    "zbar/decoder.c",
+    "zbar/error.c",
+    "zbar/image.c",
+    "zbar/img_scanner.c",
+    "zbar/refcnt.c",
+    "zbar/scanner.c",
+    "zbar/symbol.c",
+];
+
+/// Compile the ZBar sources in `ZBAR_SRC_DIR` and link them statically. Returns the version of the sources.
+#[cfg(feature = "vendored")]
+fn build_vendored() -> (u32, u32, u32) {
+    println!("cargo:rerun-if-env-changed=ZBAR_SRC_DIR");
+    let src_dir = match env::var("ZBAR_SRC_DIR") {
+        Ok(src_dir) => PathBuf::from(src_dir),
+        Err(_) => panic!(
+            "The ZBar sources are not bundled with this crate. Check out ZBar 0.23 with `git clone \
+             --depth 1 --branch 0.23 https://github.com/mchehab/zbar.git` and set ZBAR_SRC_DIR to \
+             its directory."
+        ),
+    };
+
+    if !src_dir.join("zbar/img_scanner.c").exists() {
+        panic!(
+            "The ZBar sources are not found in {}. Set ZBAR_SRC_DIR to the directory of a ZBar \
+             0.23 checkout.",
+            src_dir.to_string_lossy()
+        );
+    }
+
+    let version = vendored_version(&src_dir);
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    let mut config_h = String::from(
+        "#define NO_STATS 1\n#define HAVE_INTTYPES_H 1\n#define HAVE_STDLIB_H 1\n#define \
+         HAVE_STRING_H 1\n#define HAVE_ICONV 1\n",
+    );
+
+    config_h.push_str(&format!(
+        "#define ZBAR_VERSION_MAJOR {}\n#define ZBAR_VERSION_MINOR {}\n#define ZBAR_VERSION_PATCH \
+         {}\n",
+        version.0, version.1, version.2
+    ));
+
+    let mut build = cc::Build::new();
+
+    build
+        .include(&out_dir)
+        .include(src_dir.join("include"))
+        .include(src_dir.join("zbar"))
+        .define("HAVE_CONFIG_H", None)
+        .warnings(false);
+
+    for source in VENDORED_CORE_SOURCES {
+        build.file(src_dir.join(source));
+    }
+
+    let selected = VENDORED_SYMBOLOGIES
+        .iter()
+        .filter(|(feature, ..)| env::var(format!("CARGO_FEATURE_{}", feature)).is_ok())
+        .collect::<Vec<_>>();
+
+    // `vendored` alone builds every symbology, and the `vendored-*` features trim the build
+    let symbologies =
+        if selected.is_empty() { VENDORED_SYMBOLOGIES.iter().collect() } else { selected };
+
+    for (_, macro_name, sources) in symbologies {
+        config_h.push_str(&format!("#define {} 1\n", macro_name));
+
+        for source in sources.iter() {
+            build.file(src_dir.join(source));
+        }
+    }
+
+    fs::write(out_dir.join("config.h"), config_h).unwrap();
+
+    build.compile("zbar");
+
+    // the QR code decoder converts text with iconv, which is not a part of the C library there
+    let target = env::var("TARGET").unwrap();
+
+    if target.contains("apple") || target.contains("windows") || target.contains("freebsd") {
+        println!("cargo:rustc-link-lib=iconv");
+    }
+
+    println!("cargo:rerun-if-changed={}", src_dir.to_string_lossy());
+    println!("cargo:include={}", src_dir.join("include").to_string_lossy());
+
+    version
+}
+
+#[cfg(not(feature = "vendored"))]
+fn build_vendored() -> (u32, u32, u32) {
+    unreachable!()
+}
+
+/// Read the version of the ZBar sources from `AC_INIT` in their `configure.ac`.
+#[cfg(feature = "vendored")]
+fn vendored_version(src_dir: &std::path::Path) -> (u32, u32, u32) {
+    fs::read_to_string(src_dir.join("configure.ac"))
+        .ok()
+        .and_then(|content| {
+            let line = content.lines().find(|line| line.starts_with("AC_INIT"))?;
+
+            // AC_INIT([zbar], [0.23.93], ...)
+            let version = line.split('[').nth(2)?.split(']').next()?;
+
+            parse_version(version)
+        })
+        .unwrap_or((0, 23, 0))
+}
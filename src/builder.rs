@@ -0,0 +1,266 @@
+use crate::{ScannerConfig, ZBarConfig, ZBarImageScanner, ZBarRustError, ZBarSymbolType};
+
+/// Symbologies whose minimum and maximum data lengths can be configured.
+const VARIABLE_LENGTH: [ZBarSymbolType; 6] = [
+    ZBarSymbolType::ZBarI25,
+    ZBarSymbolType::ZBarCodeBar,
+    ZBarSymbolType::ZBarCode39,
+    ZBarSymbolType::ZBarCode93,
+    ZBarSymbolType::ZBarCode128,
+    ZBarSymbolType::ZBarPDF417,
+];
+
+/// Types which are not symbologies and cannot be enabled on their own.
+const NOT_SYMBOLOGIES: [ZBarSymbolType; 6] = [
+    ZBarSymbolType::ZBarNone,
+    ZBarSymbolType::ZBarPartial,
+    ZBarSymbolType::ZBarSymbol,
+    ZBarSymbolType::ZBarAddOn2,
+    ZBarSymbolType::ZBarAddOn5,
+    ZBarSymbolType::ZBarAddOn,
+];
+
+/// A builder of `ZBarImageScanner`s.
+///
+/// ```rust,ignore
+/// let mut scanner = ZBarImageScannerBuilder::new()
+///     .symbologies([ZBarSymbolType::ZBarQRCode, ZBarSymbolType::ZBarCode128])
+///     .min_length(ZBarSymbolType::ZBarCode128, 6)
+///     .density(2, 2)
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// The options are checked when the scanner is built. Setting lengths for a symbology which does not support them, a minimum length greater than the maximum length, or options for a symbology which is left out of the enabled set cause a `ZBarRustError::ConflictingConfig` error.
+#[derive(Debug, Clone, Default)]
+pub struct ZBarImageScannerBuilder {
+    enabled: Option<Vec<ZBarSymbolType>>,
+    options: ScannerConfig,
+}
+
+impl ZBarImageScannerBuilder {
+    /// Create a builder which keeps all the defaults of ZBar.
+    #[inline]
+    pub fn new() -> ZBarImageScannerBuilder {
+        ZBarImageScannerBuilder::default()
+    }
+
+    /// Enable only the given symbologies, and disable all the others.
+    pub fn symbologies<I: IntoIterator<Item = ZBarSymbolType>>(
+        mut self,
+        symbologies: I,
+    ) -> ZBarImageScannerBuilder {
+        let mut enabled = Vec::new();
+
+        for symbology in symbologies {
+            if !enabled.contains(&symbology) {
+                enabled.push(symbology);
+            }
+        }
+
+        self.enabled = Some(enabled);
+
+        self
+    }
+
+    /// Add a symbology to the enabled set. Once a symbology is added, all the symbologies which are not in the set are disabled.
+    pub fn enable(mut self, symbology: ZBarSymbolType) -> ZBarImageScannerBuilder {
+        let enabled = self.enabled.get_or_insert_with(Vec::new);
+
+        if !enabled.contains(&symbology) {
+            enabled.push(symbology);
+        }
+
+        self
+    }
+
+    /// Remove a symbology from the enabled set, or disable it if there is no enabled set.
+    pub fn disable(mut self, symbology: ZBarSymbolType) -> ZBarImageScannerBuilder {
+        match self.enabled.as_mut() {
+            Some(enabled) => enabled.retain(|s| *s != symbology),
+            None => self.options.set(symbology, ZBarConfig::ZBarCfgEnable, 0),
+        }
+
+        self
+    }
+
+    /// Set the minimum length of the data of a variable-length symbology.
+    #[inline]
+    pub fn min_length(mut self, symbology: ZBarSymbolType, length: u32) -> ZBarImageScannerBuilder {
+        self.options.set(symbology, ZBarConfig::ZBarCfgMinLen, length as isize);
+
+        self
+    }
+
+    /// Set the maximum length of the data of a variable-length symbology. `0` means unlimited.
+    #[inline]
+    pub fn max_length(mut self, symbology: ZBarSymbolType, length: u32) -> ZBarImageScannerBuilder {
+        self.options.set(symbology, ZBarConfig::ZBarCfgMaxLen, length as isize);
+
+        self
+    }
+
+    /// Set whether the check digit of a symbology is verified.
+    #[inline]
+    pub fn add_check(mut self, symbology: ZBarSymbolType, enable: bool) -> ZBarImageScannerBuilder {
+        self.options.set(symbology, ZBarConfig::ZBarCfgAddCheck, enable as isize);
+
+        self
+    }
+
+    /// Set whether the check digit of a symbology is kept in the decoded data.
+    #[inline]
+    pub fn emit_check(
+        mut self,
+        symbology: ZBarSymbolType,
+        enable: bool,
+    ) -> ZBarImageScannerBuilder {
+        self.options.set(symbology, ZBarConfig::ZBarCfgEmitCheck, enable as isize);
+
+        self
+    }
+
+    /// Set whether a symbology decodes the full ASCII character set.
+    #[inline]
+    pub fn ascii(mut self, symbology: ZBarSymbolType, enable: bool) -> ZBarImageScannerBuilder {
+        self.options.set(symbology, ZBarConfig::ZBarCfgASCII, enable as isize);
+
+        self
+    }
+
+    /// Set whether the data of a symbology is kept as raw bytes, instead of being converted to UTF-8. This is useful to read binary QR code payloads.
+    #[cfg(zbar_0_23)]
+    #[inline]
+    pub fn binary(mut self, symbology: ZBarSymbolType, enable: bool) -> ZBarImageScannerBuilder {
+        self.options.set(symbology, ZBarConfig::ZBarCfgBinary, enable as isize);
+
+        self
+    }
+
+    /// Set how many times a symbol of a symbology has to be decoded the same way before it is reported. Use `ZBarNone` for all symbologies.
+    #[inline]
+    pub fn uncertainty(
+        mut self,
+        symbology: ZBarSymbolType,
+        uncertainty: u32,
+    ) -> ZBarImageScannerBuilder {
+        self.options.set(symbology, ZBarConfig::ZBarCfgUncertainty, uncertainty as isize);
+
+        self
+    }
+
+    /// Set whether images are scanned again with their colors inverted when nothing is found, to find light codes on dark backgrounds.
+    #[cfg(zbar_0_23)]
+    #[inline]
+    pub fn test_inverted(mut self, enable: bool) -> ZBarImageScannerBuilder {
+        self.options.set(
+            ZBarSymbolType::ZBarNone,
+            ZBarConfig::ZBarCfgTestInverted,
+            enable as isize,
+        );
+
+        self
+    }
+
+    /// Set the scan densities, that is how many columns and rows are skipped between two scan lines. `1` scans every column or row, and `0` disables scanning in that direction.
+    #[inline]
+    pub fn density(mut self, x: u32, y: u32) -> ZBarImageScannerBuilder {
+        self.options.set(ZBarSymbolType::ZBarNone, ZBarConfig::ZBarCfgXDensity, x as isize);
+        self.options.set(ZBarSymbolType::ZBarNone, ZBarConfig::ZBarCfgYDensity, y as isize);
+
+        self
+    }
+
+    /// Set whether the positions of symbols are collected.
+    #[inline]
+    pub fn position(mut self, enable: bool) -> ZBarImageScannerBuilder {
+        self.options.set(ZBarSymbolType::ZBarNone, ZBarConfig::ZBarCfgPosition, enable as isize);
+
+        self
+    }
+
+    /// Check the options and turn them into the settings a scanner is built with.
+    pub fn to_config(&self) -> Result<ScannerConfig, ZBarRustError> {
+        let conflict = |symbology: ZBarSymbolType, reason: &'static str| {
+            Err(ZBarRustError::ConflictingConfig {
+                symbology,
+                reason,
+            })
+        };
+
+        let mut config = ScannerConfig::new();
+
+        if let Some(enabled) = self.enabled.as_ref() {
+            if enabled.is_empty() {
+                return conflict(ZBarSymbolType::ZBarNone, "no symbology is enabled");
+            }
+
+            config.set(ZBarSymbolType::ZBarNone, ZBarConfig::ZBarCfgEnable, 0);
+
+            for symbology in enabled.iter().copied() {
+                if NOT_SYMBOLOGIES.contains(&symbology) {
+                    return conflict(symbology, "not a symbology which can be enabled");
+                }
+
+                config.set(symbology, ZBarConfig::ZBarCfgEnable, 1);
+            }
+        }
+
+        for (symbology, cfg, value) in self.options.iter() {
+            // the enabled set decides which symbologies are enabled
+            if cfg == ZBarConfig::ZBarCfgEnable && self.enabled.is_some() {
+                continue;
+            }
+
+            if symbology != ZBarSymbolType::ZBarNone {
+                if let Some(enabled) = self.enabled.as_ref() {
+                    if !enabled.contains(&symbology) {
+                        return conflict(symbology, "the symbology is not enabled");
+                    }
+                }
+            }
+
+            let is_length = matches!(cfg, ZBarConfig::ZBarCfgMinLen | ZBarConfig::ZBarCfgMaxLen);
+
+            if is_length && !VARIABLE_LENGTH.contains(&symbology) {
+                return conflict(symbology, "the symbology has no length limits");
+            }
+
+            config.set(symbology, cfg, value);
+        }
+
+        for symbology in VARIABLE_LENGTH {
+            let min = self.options.get(symbology, ZBarConfig::ZBarCfgMinLen);
+            let max = self.options.get(symbology, ZBarConfig::ZBarCfgMaxLen);
+
+            if let (Some(min), Some(max)) = (min, max) {
+                if max > 0 && min > max {
+                    return conflict(
+                        symbology,
+                        "the minimum length is greater than the maximum length",
+                    );
+                }
+            }
+        }
+
+        let x_density = self.options.get(ZBarSymbolType::ZBarNone, ZBarConfig::ZBarCfgXDensity);
+        let y_density = self.options.get(ZBarSymbolType::ZBarNone, ZBarConfig::ZBarCfgYDensity);
+
+        if x_density == Some(0) && y_density == Some(0) {
+            return conflict(ZBarSymbolType::ZBarNone, "both scan densities are zero");
+        }
+
+        Ok(config)
+    }
+
+    /// Check the options and create a scanner with them.
+    pub fn build(&self) -> Result<ZBarImageScanner, ZBarRustError> {
+        let config = self.to_config()?;
+
+        let mut scanner = ZBarImageScanner::try_new()?;
+
+        scanner.apply_config(&config)?;
+
+        Ok(scanner)
+    }
+}
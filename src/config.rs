@@ -0,0 +1,319 @@
+use std::str::FromStr;
+
+use crate::{ZBarConfig, ZBarImageScanner, ZBarRustError, ZBarSymbolType};
+
+/// Symbology names in the order ZBar tries them, with the shortest abbreviation ZBar accepts.
+const SYMBOLOGIES: &[(&str, usize, ZBarSymbolType)] = &[
+    ("qrcode", 2, ZBarSymbolType::ZBarQRCode),
+    #[cfg(zbar_0_23)]
+    ("sqcode", 2, ZBarSymbolType::ZBarSQCode),
+    ("db", 2, ZBarSymbolType::ZBarDataBar),
+    ("upca", 3, ZBarSymbolType::ZBarUPCA),
+    ("upce", 3, ZBarSymbolType::ZBarUPCE),
+    ("ean13", 3, ZBarSymbolType::ZBarEAN13),
+    ("ean8", 3, ZBarSymbolType::ZBarEAN8),
+    ("ean5", 3, ZBarSymbolType::ZBarEAN5),
+    ("ean2", 3, ZBarSymbolType::ZBarEAN2),
+    ("composite", 3, ZBarSymbolType::ZBarComposite),
+    ("i25", 3, ZBarSymbolType::ZBarI25),
+    ("scanner", 4, ZBarSymbolType::ZBarPartial),
+    ("isbn13", 4, ZBarSymbolType::ZBarISBN13),
+    ("isbn10", 4, ZBarSymbolType::ZBarISBN10),
+    ("db-exp", 4, ZBarSymbolType::ZBarDataBarExp),
+    ("codabar", 4, ZBarSymbolType::ZBarCodeBar),
+    ("code93", 6, ZBarSymbolType::ZBarCode93),
+    ("code39", 6, ZBarSymbolType::ZBarCode39),
+    ("pdf417", 6, ZBarSymbolType::ZBarPDF417),
+    ("code128", 7, ZBarSymbolType::ZBarCode128),
+    ("databar", 7, ZBarSymbolType::ZBarDataBar),
+    ("databar-exp", 7, ZBarSymbolType::ZBarDataBarExp),
+];
+
+/// Config names in the order ZBar tries them, with the shortest abbreviation ZBar accepts, and whether they negate the value.
+const CONFIGS: &[(&str, usize, (ZBarConfig, bool))] = &[
+    ("y-density", 1, (ZBarConfig::ZBarCfgYDensity, false)),
+    ("x-density", 1, (ZBarConfig::ZBarCfgXDensity, false)),
+    ("enable", 2, (ZBarConfig::ZBarCfgEnable, false)),
+    ("disable", 3, (ZBarConfig::ZBarCfgEnable, true)),
+    ("min-length", 3, (ZBarConfig::ZBarCfgMinLen, false)),
+    ("max-length", 3, (ZBarConfig::ZBarCfgMaxLen, false)),
+    ("ascii", 3, (ZBarConfig::ZBarCfgASCII, false)),
+    #[cfg(zbar_0_23)]
+    ("binary", 3, (ZBarConfig::ZBarCfgBinary, false)),
+    ("add-check", 3, (ZBarConfig::ZBarCfgAddCheck, false)),
+    ("emit-check", 3, (ZBarConfig::ZBarCfgEmitCheck, false)),
+    ("uncertainty", 3, (ZBarConfig::ZBarCfgUncertainty, false)),
+    #[cfg(zbar_0_23)]
+    ("test-inverted", 3, (ZBarConfig::ZBarCfgTestInverted, false)),
+    ("position", 5, (ZBarConfig::ZBarCfgPosition, false)),
+];
+
+/// Parse a ZBar config string, like the ones `zbarimg --set` takes, into the arguments of `ZBarImageScanner::set_config`.
+///
+/// The grammar is `[symbology.]config[=value]`, the same as the one of `zbar_parse_config`:
+///
+/// * `symbology` is a name like `ean13` or `qrcode`, or `*` for all symbologies. It can be omitted to configure all symbologies.
+/// * `config` is a name like `enable` or `min-length`, optionally prefixed with `no-` to negate its value. `disable` is the same as `no-enable`.
+/// * `value` is a decimal, hexadecimal (`0x`) or octal (`0`) integer. It defaults to `1`.
+///
+/// Names can be abbreviated as long as ZBar can tell them apart, for example `ean13.en` or `q.min=4`.
+///
+/// A value without digits, such as `min-length=` or `min-length=-0x`, is `0`, as with ZBar. Unlike ZBar, characters after the value are rejected instead of being ignored. Errors report the byte offset in `config` where parsing failed.
+pub fn parse_config(config: &str) -> Result<(ZBarSymbolType, ZBarConfig, isize), ZBarRustError> {
+    let invalid = |position: usize, reason: &'static str| ZBarRustError::InvalidConfigString {
+        position,
+        reason,
+    };
+
+    let (name, value) = match config.find('=') {
+        Some(index) => (&config[..index], Some((index + 1, &config[index + 1..]))),
+        None => (config, None),
+    };
+
+    let (symbology, name, mut offset) = match name.find('.') {
+        Some(index) => {
+            let symbology = &name[..index];
+
+            let symbology = if symbology.is_empty() || symbology == "*" {
+                ZBarSymbolType::ZBarNone
+            } else {
+                lookup(SYMBOLOGIES, symbology).ok_or(invalid(0, "unknown symbology"))?
+            };
+
+            (symbology, &name[index + 1..], index + 1)
+        },
+        None => (ZBarSymbolType::ZBarNone, name, 0),
+    };
+
+    let mut negate = false;
+
+    let name = match name.strip_prefix("no-") {
+        Some(stripped) if !stripped.is_empty() => {
+            negate = true;
+            offset += 3;
+
+            stripped
+        },
+        _ => name,
+    };
+
+    if name.is_empty() {
+        return Err(invalid(offset, "missing config name"));
+    }
+
+    let (config, negated) = lookup(CONFIGS, name).ok_or(invalid(offset, "unknown config"))?;
+
+    let value = match value {
+        Some((position, value)) => {
+            parse_value(value).map_err(|(index, reason)| invalid(position + index, reason))?
+        },
+        None => 1,
+    };
+
+    let value = if negate != negated { (value == 0) as isize } else { value };
+
+    Ok((symbology, config, value))
+}
+
+fn lookup<T: Copy>(table: &[(&str, usize, T)], name: &str) -> Option<T> {
+    table
+        .iter()
+        .find(|(full_name, min_length, _)| name.len() >= *min_length && full_name.starts_with(name))
+        .map(|(_, _, item)| *item)
+}
+
+/// Parse an integer like `strtol` with the base 0, which has to fit in a C `int`. Returns the offset of the first invalid character on failure.
+fn parse_value(value: &str) -> Result<isize, (usize, &'static str)> {
+    let bytes = value.as_bytes();
+
+    let (negative, mut index) = match bytes.first() {
+        Some(b'-') => (true, 1),
+        Some(b'+') => (false, 1),
+        _ => (false, 0),
+    };
+
+    let digits = &bytes[index..];
+
+    // `strtol` reads a value without digits, such as an empty one or `0x`, as 0
+    if digits.is_empty() || digits.eq_ignore_ascii_case(b"0x") {
+        return Ok(0);
+    }
+
+    let radix = if bytes[index..].starts_with(b"0x") || bytes[index..].starts_with(b"0X") {
+        index += 2;
+
+        16
+    } else if bytes[index..].starts_with(b"0") && bytes.len() > index + 1 {
+        index += 1;
+
+        8
+    } else {
+        10
+    };
+
+    let mut magnitude = 0i64;
+
+    for (i, c) in value[index..].char_indices() {
+        let digit = c.to_digit(radix).ok_or((index + i, "invalid value"))? as i64;
+
+        magnitude = magnitude * radix as i64 + digit;
+
+        if magnitude > i32::MAX as i64 + 1 {
+            return Err((0, "value out of range"));
+        }
+    }
+
+    let value = if negative { -magnitude } else { magnitude };
+
+    if value > i32::MAX as i64 {
+        return Err((0, "value out of range"));
+    }
+
+    Ok(value as isize)
+}
+
+/// A snapshot of the settings of a `ZBarImageScanner`, as the `set_config` calls which changed them from the defaults of ZBar, in order.
+///
+/// It can be written with `to_config_string` and read with `parse` as config strings separated by spaces, such as `disable qrcode.enable=1 qrcode.min-length=4`, and with the `serde` feature it is serialized as such a string.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct ScannerConfig {
+    settings: Vec<(ZBarSymbolType, ZBarConfig, isize)>,
+}
+
+impl ScannerConfig {
+    /// Create an empty snapshot, which keeps all the defaults of ZBar.
+    #[inline]
+    pub const fn new() -> ScannerConfig {
+        ScannerConfig {
+            settings: Vec::new()
+        }
+    }
+
+    /// Record a setting. It replaces the earlier settings it overrides, that is the ones of the same config for the same symbology, or for any symbology if `symbology` is `ZBarNone`.
+    pub fn set(&mut self, symbology: ZBarSymbolType, config: ZBarConfig, value: isize) {
+        self.settings.retain(|(s, c, _)| {
+            *c != config || (*s != symbology && symbology != ZBarSymbolType::ZBarNone)
+        });
+
+        self.settings.push((symbology, config, value));
+    }
+
+    /// Get the recorded value of a config of a symbology, taking settings for all symbologies into account. Returns `None` if the config has not been changed from the default.
+    pub fn get(&self, symbology: ZBarSymbolType, config: ZBarConfig) -> Option<isize> {
+        self.settings
+            .iter()
+            .rev()
+            .find(|(s, c, _)| *c == config && (*s == symbology || *s == ZBarSymbolType::ZBarNone))
+            .map(|(_, _, value)| *value)
+    }
+
+    /// The recorded settings, in the order they have to be applied.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (ZBarSymbolType, ZBarConfig, isize)> + '_ {
+        self.settings.iter().copied()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.settings.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.settings.is_empty()
+    }
+
+    /// Write the settings as config strings separated by spaces, which can be parsed back with `parse`.
+    ///
+    /// Settings of symbologies and configs which have no name in config strings, such as `ZBarAddOn2`, `ZBarCode32` or `ZBarCfgNum`, cannot be written, so a `ZBarRustError::InvalidConfig` error is returned for them. ZBar rejects such settings anyway.
+    pub fn to_config_string(&self) -> Result<String, ZBarRustError> {
+        let mut s = String::new();
+
+        for (symbology, config, value) in self.iter() {
+            let invalid = || ZBarRustError::InvalidConfig {
+                symbology,
+                config,
+                value,
+            };
+
+            if !s.is_empty() {
+                s.push(' ');
+            }
+
+            if symbology != ZBarSymbolType::ZBarNone {
+                s.push_str(symbology_name(symbology).ok_or_else(invalid)?);
+                s.push('.');
+            }
+
+            s.push_str(config_name(config).ok_or_else(invalid)?);
+            s.push_str(&format!("={value}"));
+        }
+
+        Ok(s)
+    }
+
+    /// Apply the settings to a scanner, in order.
+    pub fn apply(&self, scanner: &mut ZBarImageScanner) -> Result<(), ZBarRustError> {
+        for (symbology, config, value) in self.iter() {
+            scanner.set_config(symbology, config, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The full name of a symbology in config strings.
+fn symbology_name(symbology: ZBarSymbolType) -> Option<&'static str> {
+    SYMBOLOGIES.iter().rev().find(|(_, _, s)| *s == symbology).map(|(name, ..)| *name)
+}
+
+/// The full name of a config in config strings.
+fn config_name(config: ZBarConfig) -> Option<&'static str> {
+    CONFIGS.iter().find(|(_, _, c)| *c == (config, false)).map(|(name, ..)| *name)
+}
+
+impl FromStr for ScannerConfig {
+    type Err = ZBarRustError;
+
+    /// Parse config strings separated by whitespace. Error positions are byte offsets in the whole string.
+    fn from_str(s: &str) -> Result<ScannerConfig, ZBarRustError> {
+        let mut config = ScannerConfig::new();
+
+        for setting in s.split_whitespace() {
+            let offset = setting.as_ptr() as usize - s.as_ptr() as usize;
+
+            let (symbology, cfg, value) = parse_config(setting).map_err(|error| match error {
+                ZBarRustError::InvalidConfigString {
+                    position,
+                    reason,
+                } => ZBarRustError::InvalidConfigString {
+                    position: offset + position,
+                    reason,
+                },
+                error => error,
+            })?;
+
+            config.set(symbology, cfg, value);
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ScannerConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let s = self.to_config_string().map_err(serde::ser::Error::custom)?;
+
+        serializer.serialize_str(&s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ScannerConfig {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
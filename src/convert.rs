@@ -0,0 +1,121 @@
+use std::borrow::Cow;
+
+use crate::{FourCC, FourCCPlane, ZBarRustError};
+
+/// Compute the luminance of packed RGB pixels with the BT.601 weights.
+///
+/// `channels` are the indices of the red, green and blue samples within a pixel of `bytes_per_pixel` bytes.
+pub(crate) fn packed_rgb_to_luma(
+    data: &[u8],
+    bytes_per_pixel: usize,
+    channels: [usize; 3],
+) -> Vec<u8> {
+    let [r, g, b] = channels;
+
+    data.chunks_exact(bytes_per_pixel)
+        .map(|pixel| {
+            let y = 77 * pixel[r] as u32 + 150 * pixel[g] as u32 + 29 * pixel[b] as u32;
+
+            ((y + 0x80) >> 8) as u8
+        })
+        .collect()
+}
+
+/// Extract the luminance samples of packed YUV 4:2:2 data. `luma_first` means that every pair of bytes starts with a luminance sample, like `YUYV`.
+pub(crate) fn packed_yuv_to_luma(data: &[u8], width: usize, luma_first: bool) -> Vec<u8> {
+    let offset = if luma_first { 0 } else { 1 };
+
+    // rows are padded to an even number of pixels
+    let row_bytes = width.div_ceil(2) * 4;
+
+    if row_bytes == 0 {
+        return Vec::new();
+    }
+
+    let mut luma = Vec::with_capacity(width * (data.len() / row_bytes));
+
+    for row in data.chunks_exact(row_bytes) {
+        luma.extend(row.iter().skip(offset).step_by(2).take(width));
+    }
+
+    luma
+}
+
+/// Check that `data` holds an image whose plane `i` starts at the byte `planes[i].0` and has a row every `planes[i].1` bytes. Returns the image with tightly packed rows, or only its first plane if `luma_only` is set.
+///
+/// The data is only copied if some of the returned planes have padded rows, or do not follow each other. Bytes before, between and after the planes are allowed.
+pub(crate) fn pack_rows<'a>(
+    data: &'a [u8],
+    format: FourCC,
+    width: u32,
+    height: u32,
+    planes: &[(usize, usize)],
+    luma_only: bool,
+) -> Result<Cow<'a, [u8]>, ZBarRustError> {
+    let geometries =
+        format.planes(width, height).ok_or(ZBarRustError::UnsupportedFormat(format))?;
+
+    if planes.len() != geometries.len() {
+        return Err(ZBarRustError::StrideCountMismatch {
+            expected: geometries.len(),
+            actual:   planes.len(),
+        });
+    }
+
+    let mut required = 0usize;
+
+    for (index, (geometry, (offset, stride))) in
+        geometries.iter().zip(planes.iter().copied()).enumerate()
+    {
+        if stride < geometry.row_bytes {
+            return Err(ZBarRustError::StrideTooSmall {
+                plane: index,
+                stride,
+                row_bytes: geometry.row_bytes,
+            });
+        }
+
+        if geometry.rows > 0 {
+            // the last row does not need to be padded
+            let end = offset
+                .saturating_add(stride.saturating_mul(geometry.rows - 1))
+                .saturating_add(geometry.row_bytes);
+
+            required = required.max(end);
+        }
+    }
+
+    if data.len() < required {
+        return Err(ZBarRustError::BufferSizeMismatch {
+            expected: required, actual: data.len()
+        });
+    }
+
+    let kept = if luma_only { &geometries[..1] } else { &geometries[..] };
+
+    let start = planes[0].0;
+    let mut contiguous = true;
+    let mut end = start;
+
+    for (geometry, (offset, stride)) in kept.iter().zip(planes.iter().copied()) {
+        contiguous &= offset == end && stride == geometry.row_bytes;
+
+        end = end.saturating_add(geometry.len());
+    }
+
+    if contiguous {
+        return Ok(Cow::Borrowed(&data[start..end]));
+    }
+
+    let mut packed = Vec::with_capacity(kept.iter().map(FourCCPlane::len).sum());
+
+    for (geometry, (offset, stride)) in kept.iter().zip(planes.iter().copied()) {
+        for row in 0..geometry.rows {
+            let start = offset + row * stride;
+
+            packed.extend_from_slice(&data[start..start + geometry.row_bytes]);
+        }
+    }
+
+    Ok(Cow::Owned(packed))
+}
@@ -0,0 +1,206 @@
+use std::{ptr, slice};
+
+use enum_ordinalize::Ordinalize;
+use libc::{c_int, c_uint};
+
+#[cfg(feature = "dynamic-loading")]
+use crate::load_library;
+use crate::{
+    sys::*, ZBarColor, ZBarConfig, ZBarConfigSet, ZBarModifierSet, ZBarRustError, ZBarSymbolType,
+};
+
+/// A bar width stream decoder, which decodes 1D symbols from the widths of their bars and spaces, such as the output of a laser scanner or a line sensor.
+///
+/// Widths are fed in order with `decode_width`, starting with a space, and can be in any unit as long as it is the same for the whole scan. When a symbol is decoded, its type and data can be read until the next width is fed.
+///
+/// ```rust,ignore
+/// let mut decoder = ZBarDecoder::new();
+///
+/// for width in widths {
+///     match decoder.decode_width(width) {
+///         ZBarSymbolType::ZBarNone | ZBarSymbolType::ZBarPartial => (),
+///         symbol_type => println!("{:?}: {:?}", symbol_type, decoder.data()),
+///     }
+/// }
+/// ```
+pub struct ZBarDecoder {
+    decoder: *mut zbar_decoder_t,
+}
+
+impl ZBarDecoder {
+    /// Create a decoder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if ZBar cannot allocate the decoder. Use `try_new` to handle that case.
+    pub fn new() -> ZBarDecoder {
+        ZBarDecoder::try_new().unwrap()
+    }
+
+    /// Create a decoder.
+    pub fn try_new() -> Result<ZBarDecoder, ZBarRustError> {
+        #[cfg(feature = "dynamic-loading")]
+        load_library()?;
+
+        let decoder = unsafe { zbar_decoder_create() };
+
+        if decoder.is_null() {
+            return Err(ZBarRustError::NullHandle("zbar_decoder_create"));
+        }
+
+        Ok(ZBarDecoder {
+            decoder,
+        })
+    }
+
+    /// The raw `zbar_decoder_t` handle, which stays owned by this decoder.
+    #[inline]
+    pub(crate) fn as_ptr(&self) -> *mut zbar_decoder_t {
+        self.decoder
+    }
+
+    pub fn set_config(
+        &mut self,
+        symbology: ZBarSymbolType,
+        config: ZBarConfig,
+        value: isize,
+    ) -> Result<(), ZBarRustError> {
+        let result = unsafe {
+            zbar_decoder_set_config(
+                self.decoder,
+                symbology.to_raw() as c_int,
+                config.ordinal() as c_int,
+                value as c_int,
+            )
+        };
+
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(ZBarRustError::InvalidConfig {
+                symbology,
+                config,
+                value,
+            })
+        }
+    }
+
+    /// Read the current value of a config of a symbology. This is only available with ZBar 0.22 or later.
+    #[cfg(zbar_0_22)]
+    pub fn get_config(
+        &self,
+        symbology: ZBarSymbolType,
+        config: ZBarConfig,
+    ) -> Result<isize, ZBarRustError> {
+        let mut value = 0;
+
+        let result = unsafe {
+            zbar_decoder_get_config(
+                self.decoder,
+                symbology.to_raw() as c_int,
+                config.ordinal() as c_int,
+                &mut value,
+            )
+        };
+
+        if result == 0 {
+            Ok(value as isize)
+        } else {
+            Err(ZBarRustError::UnsupportedConfig {
+                symbology,
+                config,
+            })
+        }
+    }
+
+    /// The boolean configs which are enabled for a symbology.
+    #[inline]
+    pub fn configs(&self, symbology: ZBarSymbolType) -> ZBarConfigSet {
+        ZBarConfigSet::from_bits(unsafe {
+            zbar_decoder_get_configs(self.decoder, symbology.to_raw() as c_int)
+        })
+    }
+
+    /// Clear the state of the decoder, including any partially decoded symbol.
+    #[inline]
+    pub fn reset(&mut self) {
+        unsafe { zbar_decoder_reset(self.decoder) }
+    }
+
+    /// Mark the start of a new scan pass, so that the widths fed afterwards are not joined with the previous ones. Decoded data is kept until the next width is fed.
+    #[inline]
+    pub fn new_scan(&mut self) {
+        unsafe { zbar_decoder_new_scan(self.decoder) }
+    }
+
+    /// Feed the width of the next bar or space. Returns the type of the symbol which has just been decoded, `ZBarPartial` if a symbol has been partly decoded, or `ZBarNone` if there is nothing yet.
+    #[inline]
+    pub fn decode_width(&mut self, width: u32) -> ZBarSymbolType {
+        ZBarSymbolType::from_raw(unsafe { zbar_decode_width(self.decoder, width as c_uint) })
+    }
+
+    /// Whether the last width fed was of a bar or a space.
+    #[inline]
+    pub fn color(&self) -> ZBarColor {
+        let color = unsafe { zbar_decoder_get_color(self.decoder) };
+
+        ZBarColor::from_ordinal(color as isize).unwrap_or(ZBarColor::ZBarSpace)
+    }
+
+    /// The type of the last decoded symbol.
+    #[inline]
+    pub fn symbol_type(&self) -> ZBarSymbolType {
+        ZBarSymbolType::from_raw(unsafe { zbar_decoder_get_type(self.decoder) })
+    }
+
+    /// The data of the last decoded symbol. It is only valid until the next width is fed.
+    pub fn data(&self) -> &[u8] {
+        unsafe {
+            let data = zbar_decoder_get_data(self.decoder);
+
+            if data.is_null() {
+                return &[];
+            }
+
+            let data_length = zbar_decoder_get_data_length(self.decoder) as usize;
+
+            slice::from_raw_parts(data as *const u8, data_length)
+        }
+    }
+
+    /// The modifiers applied to the last decoded symbol.
+    #[inline]
+    pub fn modifiers(&self) -> ZBarModifierSet {
+        ZBarModifierSet::from_bits(unsafe { zbar_decoder_get_modifiers(self.decoder) })
+    }
+
+    /// The direction in which the last symbol was read, `1` forward and `-1` backward. `0` means the direction is unknown.
+    #[inline]
+    pub fn direction(&self) -> i32 {
+        unsafe { zbar_decoder_get_direction(self.decoder) as i32 }
+    }
+
+    pub fn destroy(mut self) {
+        unsafe {
+            zbar_decoder_destroy(self.decoder);
+            self.decoder = ptr::null_mut();
+        }
+    }
+}
+
+impl Default for ZBarDecoder {
+    #[inline]
+    fn default() -> Self {
+        ZBarDecoder::new()
+    }
+}
+
+impl Drop for ZBarDecoder {
+    fn drop(&mut self) {
+        if !self.decoder.is_null() {
+            unsafe {
+                zbar_decoder_destroy(self.decoder);
+            }
+        }
+    }
+}
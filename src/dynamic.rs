@@ -0,0 +1,96 @@
+use std::{
+    ffi::{OsStr, OsString},
+    sync::OnceLock,
+};
+
+use libc::{c_int, c_uint};
+use libloading::Library;
+
+use crate::{version::check_compatible, ZBarRustError};
+
+/// The file names tried by `load_library`, in order.
+#[cfg(target_os = "macos")]
+const LIBRARY_NAMES: [&str; 2] = ["libzbar.0.dylib", "libzbar.dylib"];
+#[cfg(windows)]
+const LIBRARY_NAMES: [&str; 2] = ["libzbar-0.dll", "zbar.dll"];
+#[cfg(not(any(target_os = "macos", windows)))]
+const LIBRARY_NAMES: [&str; 2] = ["libzbar.so.0", "libzbar.so"];
+
+static LIBRARY: OnceLock<Result<Library, ZBarRustError>> = OnceLock::new();
+
+/// Load the ZBar library, `libzbar.so.0` on Linux, if it has not been loaded yet. This is only available with the `dynamic-loading` feature.
+///
+/// The library is only accepted if it has the APIs of the version this crate was compiled against, as `check_version` checks, otherwise a `ZBarRustError::VersionMismatch` error is returned. That way every function which the bindings declare can be found in it.
+///
+/// The safe wrappers load the library by themselves and return a `ZBarRustError::LibraryUnavailable` or `ZBarRustError::VersionMismatch` error if it cannot be used. The raw `zbar_*` functions panic instead, so call this function first to check that ZBar is available before using them.
+#[inline]
+pub fn load_library() -> Result<(), ZBarRustError> {
+    library().map(|_| ())
+}
+
+/// Load the ZBar library from a specific path, instead of searching for it. This is only available with the `dynamic-loading` feature.
+///
+/// It has to be called before ZBar is used. If a library has already been loaded, only the result of that loading is returned.
+pub fn load_library_from<P: AsRef<OsStr>>(path: P) -> Result<(), ZBarRustError> {
+    LIBRARY
+        .get_or_init(|| open(&[path.as_ref().to_os_string()]))
+        .as_ref()
+        .map(|_| ())
+        .map_err(Clone::clone)
+}
+
+fn library() -> Result<&'static Library, ZBarRustError> {
+    LIBRARY.get_or_init(|| open(&LIBRARY_NAMES.map(OsString::from))).as_ref().map_err(Clone::clone)
+}
+
+fn open(names: &[OsString]) -> Result<Library, ZBarRustError> {
+    let mut reasons = Vec::with_capacity(names.len());
+
+    for name in names {
+        match unsafe { Library::new(name) } {
+            Ok(library) => {
+                check_library_version(&library)?;
+
+                return Ok(library);
+            },
+            Err(error) => reasons.push(error.to_string()),
+        }
+    }
+
+    Err(ZBarRustError::LibraryUnavailable(reasons.join("; ")))
+}
+
+/// Check the version of a library before accepting it, because looking up the functions of a newer version in an older library would panic.
+fn check_library_version(library: &Library) -> Result<(), ZBarRustError> {
+    type ZBarVersion = unsafe extern "C" fn(*mut c_uint, *mut c_uint, *mut c_uint) -> c_int;
+
+    let zbar_version = unsafe { library.get::<ZBarVersion>(b"zbar_version\0") }
+        .map_err(|error| ZBarRustError::LibraryUnavailable(error.to_string()))?;
+
+    let mut major: c_uint = 0;
+    let mut minor: c_uint = 0;
+    let mut patch: c_uint = 0;
+
+    // older versions only take the first two arguments and ignore the third one
+    unsafe {
+        zbar_version(&mut major, &mut minor, &mut patch);
+    }
+
+    check_compatible((major as u32, minor as u32, patch as u32))
+}
+
+/// Look up a function of the ZBar library. `name` has to end with a NUL byte.
+///
+/// # Panics
+///
+/// Panics if the library cannot be loaded or does not export the function.
+pub(crate) fn function<T: Copy>(name: &str) -> T {
+    let library = library().unwrap_or_else(|error| panic!("{error}"));
+
+    match unsafe { library.get::<T>(name.as_bytes()) } {
+        Ok(function) => *function,
+        Err(error) => {
+            panic!("the ZBar library does not export `{}`: {error}", name.trim_end_matches('\0'))
+        },
+    }
+}
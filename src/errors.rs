@@ -0,0 +1,144 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::{FourCC, ZBarConfig, ZBarError, ZBarRegion, ZBarSymbolType};
+
+/// Errors returned by the safe ZBar wrappers.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ZBarRustError {
+    /// A `zbar_*_create` function returned a null handle. The payload is the name of that function.
+    NullHandle(&'static str),
+    /// ZBar rejected a configuration, or it cannot be written as a config string.
+    InvalidConfig { symbology: ZBarSymbolType, config: ZBarConfig, value: isize },
+    /// The options of a `ZBarImageScannerBuilder` contradict each other.
+    ConflictingConfig { symbology: ZBarSymbolType, reason: &'static str },
+    /// The config of the symbology cannot be read back.
+    UnsupportedConfig { symbology: ZBarSymbolType, config: ZBarConfig },
+    /// A config string could not be parsed. `position` is the byte offset where parsing failed.
+    InvalidConfigString { position: usize, reason: &'static str },
+    /// The pixel format is not supported by the operation.
+    UnsupportedFormat(FourCC),
+    /// The length of the image data does not match the image size and format.
+    BufferSizeMismatch { expected: usize, actual: usize },
+    /// The number of row strides does not match the number of planes of the pixel format.
+    StrideCountMismatch { expected: usize, actual: usize },
+    /// The row stride of a plane is smaller than the number of bytes of its rows.
+    StrideTooSmall { plane: usize, stride: usize, row_bytes: usize },
+    /// The image has no data attached.
+    NoImageData,
+    /// The region does not lie within the image.
+    RegionOutOfBounds { region: ZBarRegion, width: u32, height: u32 },
+    /// The loaded ZBar library does not match the version this crate was compiled against.
+    VersionMismatch { compiled: (u32, u32, u32), loaded: (u32, u32, u32) },
+    /// The ZBar library could not be loaded at runtime. The payload explains why.
+    LibraryUnavailable(String),
+    /// An error code reported by the ZBar library.
+    Library(ZBarError),
+}
+
+impl From<ZBarError> for ZBarRustError {
+    #[inline]
+    fn from(error: ZBarError) -> Self {
+        ZBarRustError::Library(error)
+    }
+}
+
+impl Display for ZBarRustError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ZBarRustError::NullHandle(function) => {
+                f.write_fmt(format_args!("`{function}` returned a null handle"))
+            },
+            ZBarRustError::InvalidConfig {
+                symbology,
+                config,
+                value,
+            } => f.write_fmt(format_args!(
+                "invalid config {config:?} = {value} for the symbology {symbology:?}"
+            )),
+            ZBarRustError::ConflictingConfig {
+                symbology,
+                reason,
+            } => f.write_fmt(format_args!("conflicting config for {symbology:?}: {reason}")),
+            ZBarRustError::UnsupportedConfig {
+                symbology,
+                config,
+            } => f.write_fmt(format_args!(
+                "the config {config:?} of the symbology {symbology:?} cannot be read"
+            )),
+            ZBarRustError::InvalidConfigString {
+                position,
+                reason,
+            } => f.write_fmt(format_args!("invalid config string at byte {position}: {reason}")),
+            ZBarRustError::UnsupportedFormat(format) => {
+                f.write_fmt(format_args!("unsupported image format {format}"))
+            },
+            ZBarRustError::BufferSizeMismatch {
+                expected,
+                actual,
+            } => f.write_fmt(format_args!(
+                "the image data should be {expected} bytes long, but it is {actual} bytes long"
+            )),
+            ZBarRustError::StrideCountMismatch {
+                expected,
+                actual,
+            } => f.write_fmt(format_args!(
+                "the pixel format has {expected} planes, but {actual} are given"
+            )),
+            ZBarRustError::StrideTooSmall {
+                plane,
+                stride,
+                row_bytes,
+            } => f.write_fmt(format_args!(
+                "the stride of the plane {plane} is {stride} bytes, which is smaller than its \
+                 rows of {row_bytes} bytes"
+            )),
+            ZBarRustError::NoImageData => f.write_str("the image has no data"),
+            ZBarRustError::RegionOutOfBounds {
+                region,
+                width,
+                height,
+            } => f.write_fmt(format_args!(
+                "the region {}x{} at ({}, {}) does not lie within the {width}x{height} image",
+                region.width, region.height, region.x, region.y
+            )),
+            ZBarRustError::VersionMismatch {
+                compiled,
+                loaded,
+            } => f.write_fmt(format_args!(
+                "compiled against ZBar {}.{}.{}, but ZBar {}.{}.{} is loaded",
+                compiled.0, compiled.1, compiled.2, loaded.0, loaded.1, loaded.2
+            )),
+            ZBarRustError::LibraryUnavailable(reason) => {
+                f.write_fmt(format_args!("the ZBar library cannot be loaded: {reason}"))
+            },
+            ZBarRustError::Library(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl Error for ZBarRustError {}
+
+impl Display for ZBarError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str(match self {
+            ZBarError::ZBarOK => "no error",
+            ZBarError::ZBarErrNoMem => "out of memory",
+            ZBarError::ZBarErrInternal => "internal library error",
+            ZBarError::ZBarErrUnsupported => "unsupported request",
+            ZBarError::ZBarErrInvalid => "invalid request",
+            ZBarError::ZBarErrSystem => "system error",
+            ZBarError::ZBarErrLocking => "locking error",
+            ZBarError::ZBarErrBudy => "all resources busy",
+            ZBarError::ZBarErrXDisplay => "X11 display error",
+            ZBarError::ZBarErrXProto => "X11 protocol error",
+            ZBarError::ZBarErrClosed => "output window is closed",
+            ZBarError::ZBarErrWinAPI => "windows system error",
+            ZBarError::ZBarErrNum => "unknown error",
+        })
+    }
+}
+
+impl Error for ZBarError {}
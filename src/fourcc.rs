@@ -0,0 +1,375 @@
+use std::fmt::{self, Debug, Display, Formatter};
+
+use crate::ZBarRustError;
+
+/// A pixel format, identified by a four character code (fourcc) as ZBar does.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FourCC(u32);
+
+impl FourCC {
+    /// 8-bit BGR 3-3-2.
+    pub const BGR1: FourCC = FourCC::new(*b"BGR1");
+    /// 24-bit BGR, ordered as B G R.
+    pub const BGR3: FourCC = FourCC::new(*b"BGR3");
+    /// 32-bit BGR.
+    pub const BGR4: FourCC = FourCC::new(*b"BGR4");
+    /// 32-bit RGB from a bitmap with `BI_BITFIELDS` compression, which ZBar identifies by the compression number 3.
+    pub const BI_BITFIELDS: FourCC = FourCC::new([3, 0, 0, 0]);
+    /// 8-bit grayscale.
+    pub const GREY: FourCC = FourCC::new(*b"GREY");
+    /// Planar YUV 4:2:0.
+    pub const I420: FourCC = FourCC::new(*b"I420");
+    /// Planar YUV 4:2:0, the same as `I420`.
+    pub const IYUV: FourCC = FourCC::new(*b"IYUV");
+    /// JPEG compressed data. ZBar can only convert it if it is built with JPEG support.
+    pub const JPEG: FourCC = FourCC::new(*b"JPEG");
+    /// Motion JPEG compressed data, which ZBar treats as `JPEG`.
+    pub const MJPG: FourCC = FourCC::new(*b"MJPG");
+    /// A Y plane followed by an interleaved UV plane, 4:2:0.
+    pub const NV12: FourCC = FourCC::new(*b"NV12");
+    /// A Y plane followed by an interleaved UV plane, 4:2:2.
+    pub const NV16: FourCC = FourCC::new(*b"NV16");
+    /// A Y plane followed by an interleaved VU plane, 4:2:0.
+    pub const NV21: FourCC = FourCC::new(*b"NV21");
+    /// A Y plane followed by an interleaved VU plane, 4:2:2.
+    pub const NV61: FourCC = FourCC::new(*b"NV61");
+    /// Planar YUV 4:1:1.
+    pub const P411: FourCC = FourCC::new(*b"411P");
+    /// Planar YUV 4:2:2.
+    pub const P422: FourCC = FourCC::new(*b"422P");
+    /// 16-bit RGB 4-4-4, little-endian.
+    pub const R444: FourCC = FourCC::new(*b"R444");
+    /// 8-bit RGB 3-3-2.
+    pub const RGB1: FourCC = FourCC::new(*b"RGB1");
+    /// 24-bit RGB, ordered as R G B.
+    pub const RGB3: FourCC = FourCC::new(*b"RGB3");
+    /// 32-bit RGB.
+    pub const RGB4: FourCC = FourCC::new(*b"RGB4");
+    /// 16-bit RGB 5-5-5, little-endian.
+    pub const RGBO: FourCC = FourCC::new(*b"RGBO");
+    /// 16-bit RGB 5-6-5, little-endian.
+    pub const RGBP: FourCC = FourCC::new(*b"RGBP");
+    /// 16-bit RGB 5-5-5, big-endian.
+    pub const RGBQ: FourCC = FourCC::new(*b"RGBQ");
+    /// 16-bit RGB 5-6-5, big-endian.
+    pub const RGBR: FourCC = FourCC::new(*b"RGBR");
+    /// Packed YUV 4:2:2, ordered as U Y0 V Y1.
+    pub const UYVY: FourCC = FourCC::new(*b"UYVY");
+    /// Packed YUV 4:2:2, ordered as V Y0 U Y1.
+    pub const VYUY: FourCC = FourCC::new(*b"VYUY");
+    /// 8-bit grayscale, the same as `GREY`.
+    pub const Y8: FourCC = FourCC::new(*b"Y8  ");
+    /// 8-bit grayscale, the same as `GREY`.
+    pub const Y800: FourCC = FourCC::new(*b"Y800");
+    /// 8-bit grayscale, the same as `GREY`.
+    pub const Y8_NUL: FourCC = FourCC::new(*b"Y8\0\0");
+    /// Planar YUV 4:2:0, the same as `I420`.
+    pub const YU12: FourCC = FourCC::new(*b"YU12");
+    /// Treated as `YUYV` by ZBar.
+    pub const YUV4: FourCC = FourCC::new(*b"YUV4");
+    /// Planar YUV with chroma planes subsampled by 4 in both directions.
+    pub const YUV9: FourCC = FourCC::new(*b"YUV9");
+    /// The same as `YUYV`.
+    pub const YUY2: FourCC = FourCC::new(*b"YUY2");
+    /// Packed YUV 4:2:2, ordered as Y0 U Y1 V.
+    pub const YUYV: FourCC = FourCC::new(*b"YUYV");
+    /// Planar YUV 4:2:0 with the V plane before the U plane.
+    pub const YV12: FourCC = FourCC::new(*b"YV12");
+    /// The same as `YUV9` with the V plane before the U plane.
+    pub const YVU9: FourCC = FourCC::new(*b"YVU9");
+    /// Packed YUV 4:2:2, ordered as Y0 V Y1 U.
+    pub const YVYU: FourCC = FourCC::new(*b"YVYU");
+
+    /// Build a fourcc code the same way as the `zbar_fourcc` macro does.
+    #[inline]
+    pub const fn new(code: [u8; 4]) -> FourCC {
+        FourCC(u32::from_le_bytes(code))
+    }
+
+    #[inline]
+    pub const fn from_u32(code: u32) -> FourCC {
+        FourCC(code)
+    }
+
+    #[inline]
+    pub const fn to_u32(self) -> u32 {
+        self.0
+    }
+
+    /// The four characters of the code.
+    #[inline]
+    pub const fn to_bytes(self) -> [u8; 4] {
+        self.0.to_le_bytes()
+    }
+
+    /// How the pixels are laid out in memory. Returns `None` if the format is unknown to ZBar's converter.
+    #[inline]
+    pub fn layout(self) -> Option<FourCCLayout> {
+        LAYOUTS.iter().find(|(f, _)| *f == self).map(|(_, layout)| *layout)
+    }
+
+    /// The average number of bits per pixel, for example 12 for `I420` and 24 for `RGB3`. Returns `None` for unknown formats and JPEG formats.
+    pub fn bits_per_pixel(self) -> Option<u32> {
+        match self.layout()? {
+            FourCCLayout::Gray => Some(8),
+            FourCCLayout::YuvPlanar {
+                xsub2,
+                ysub2,
+                ..
+            }
+            | FourCCLayout::YuvSemiPlanar {
+                xsub2,
+                ysub2,
+                ..
+            } => Some(8 + 16 / (1 << (xsub2 + ysub2))),
+            FourCCLayout::YuvPacked {
+                ..
+            } => Some(16),
+            FourCCLayout::RgbPacked {
+                bytes_per_pixel,
+            } => Some(bytes_per_pixel as u32 * 8),
+            FourCCLayout::Jpeg => None,
+        }
+    }
+
+    /// The number of bytes per pixel of packed formats. Returns `None` for planar formats, unknown formats and JPEG formats.
+    pub fn bytes_per_pixel(self) -> Option<u32> {
+        match self.layout()? {
+            FourCCLayout::Gray => Some(1),
+            FourCCLayout::YuvPacked {
+                ..
+            } => Some(2),
+            FourCCLayout::RgbPacked {
+                bytes_per_pixel,
+            } => Some(bytes_per_pixel as u32),
+            _ => None,
+        }
+    }
+
+    /// The planes of an image of this format and size, in memory order. Subsampled planes are rounded up for odd sizes. Returns `None` for unknown formats and JPEG formats.
+    pub fn planes(self, width: u32, height: u32) -> Option<Vec<FourCCPlane>> {
+        let width = width as usize;
+        let height = height as usize;
+
+        let subsampled = |size: usize, sub2: u8| (size + (1 << sub2) - 1) >> sub2;
+
+        let planes = match self.layout()? {
+            FourCCLayout::Gray => vec![FourCCPlane::new(width, height)],
+            FourCCLayout::YuvPlanar {
+                xsub2,
+                ysub2,
+                ..
+            } => {
+                let chroma = FourCCPlane::new(subsampled(width, xsub2), subsampled(height, ysub2));
+
+                vec![FourCCPlane::new(width, height), chroma, chroma]
+            },
+            FourCCLayout::YuvSemiPlanar {
+                xsub2,
+                ysub2,
+                ..
+            } => vec![
+                FourCCPlane::new(width, height),
+                FourCCPlane::new(
+                    subsampled(width, xsub2).saturating_mul(2),
+                    subsampled(height, ysub2),
+                ),
+            ],
+            FourCCLayout::YuvPacked {
+                ..
+            } => vec![FourCCPlane::new(subsampled(width, 1).saturating_mul(4), height)],
+            FourCCLayout::RgbPacked {
+                bytes_per_pixel,
+            } => vec![FourCCPlane::new(width.saturating_mul(bytes_per_pixel as usize), height)],
+            FourCCLayout::Jpeg => return None,
+        };
+
+        Some(planes)
+    }
+
+    /// How many bytes an image of this format and size should have when its rows are tightly packed. Returns `None` for unknown formats and JPEG formats. The result saturates at `usize::MAX`.
+    pub fn data_length(self, width: u32, height: u32) -> Option<usize> {
+        self.planes(width, height)
+            .map(|planes| planes.iter().fold(0usize, |sum, plane| sum.saturating_add(plane.len())))
+    }
+}
+
+impl From<[u8; 4]> for FourCC {
+    #[inline]
+    fn from(code: [u8; 4]) -> Self {
+        FourCC::new(code)
+    }
+}
+
+impl From<u32> for FourCC {
+    #[inline]
+    fn from(code: u32) -> Self {
+        FourCC::from_u32(code)
+    }
+}
+
+impl From<FourCC> for u32 {
+    #[inline]
+    fn from(fourcc: FourCC) -> Self {
+        fourcc.to_u32()
+    }
+}
+
+impl Display for FourCC {
+    /// Show the four characters, or the hexadecimal code if some of them are not printable. Trailing NUL characters are omitted.
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        let bytes = self.to_bytes();
+
+        let trimmed = match bytes.iter().rposition(|b| *b != 0) {
+            Some(index) => &bytes[..=index],
+            None => &bytes[..0],
+        };
+
+        if !trimmed.is_empty() && trimmed.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+            f.write_str(&String::from_utf8_lossy(trimmed))
+        } else {
+            f.write_fmt(format_args!("0x{:08X}", self.0))
+        }
+    }
+}
+
+impl Debug for FourCC {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_fmt(format_args!("FourCC({self})"))
+    }
+}
+
+/// How the pixels of a `FourCC` format are laid out in memory.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FourCCLayout {
+    /// Only a luminance plane.
+    Gray,
+    /// A luminance plane followed by two chroma planes which are subsampled by `2^xsub2` horizontally and `2^ysub2` vertically. `swap_uv` means that the V plane comes first.
+    YuvPlanar { xsub2: u8, ysub2: u8, swap_uv: bool },
+    /// A luminance plane followed by one interleaved chroma plane which is subsampled by `2^xsub2` horizontally and `2^ysub2` vertically. `swap_uv` means that V samples come first.
+    YuvSemiPlanar { xsub2: u8, ysub2: u8, swap_uv: bool },
+    /// Interleaved luminance and chroma samples, two bytes per pixel. `swap_uv` means that V samples come first, and `luma_first` means that every pair starts with a luminance sample.
+    YuvPacked { swap_uv: bool, luma_first: bool },
+    /// Interleaved color samples.
+    RgbPacked { bytes_per_pixel: u8 },
+    /// Compressed JPEG data.
+    Jpeg,
+}
+
+/// The geometry of a plane of an image whose rows are tightly packed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FourCCPlane {
+    /// The number of bytes of a row.
+    pub row_bytes: usize,
+    /// The number of rows.
+    pub rows:      usize,
+}
+
+impl FourCCPlane {
+    #[inline]
+    const fn new(row_bytes: usize, rows: usize) -> FourCCPlane {
+        FourCCPlane {
+            row_bytes,
+            rows,
+        }
+    }
+
+    /// The number of bytes of the plane. It saturates at `usize::MAX`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.row_bytes.saturating_mul(self.rows)
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+const fn planar(xsub2: u8, ysub2: u8, swap_uv: bool) -> FourCCLayout {
+    FourCCLayout::YuvPlanar {
+        xsub2,
+        ysub2,
+        swap_uv,
+    }
+}
+
+const fn semi_planar(xsub2: u8, ysub2: u8, swap_uv: bool) -> FourCCLayout {
+    FourCCLayout::YuvSemiPlanar {
+        xsub2,
+        ysub2,
+        swap_uv,
+    }
+}
+
+const fn packed_yuv(swap_uv: bool, luma_first: bool) -> FourCCLayout {
+    FourCCLayout::YuvPacked {
+        swap_uv,
+        luma_first,
+    }
+}
+
+const fn packed_rgb(bytes_per_pixel: u8) -> FourCCLayout {
+    FourCCLayout::RgbPacked {
+        bytes_per_pixel,
+    }
+}
+
+/// The formats known by ZBar's converter, as listed in `format_defs` of its `convert.c`.
+const LAYOUTS: [(FourCC, FourCCLayout); 36] = [
+    (FourCC::P422, planar(1, 0, false)),
+    (FourCC::I420, planar(1, 1, false)),
+    (FourCC::IYUV, planar(1, 1, false)),
+    (FourCC::YU12, planar(1, 1, false)),
+    (FourCC::YV12, planar(1, 1, true)),
+    (FourCC::P411, planar(2, 0, false)),
+    (FourCC::YUV9, planar(2, 2, false)),
+    (FourCC::YVU9, planar(2, 2, true)),
+    (FourCC::NV12, semi_planar(1, 1, false)),
+    (FourCC::NV21, semi_planar(1, 1, true)),
+    (FourCC::NV16, semi_planar(1, 0, false)),
+    (FourCC::NV61, semi_planar(1, 0, true)),
+    (FourCC::YUYV, packed_yuv(false, true)),
+    (FourCC::UYVY, packed_yuv(false, false)),
+    (FourCC::YUY2, packed_yuv(false, true)),
+    (FourCC::YUV4, packed_yuv(false, true)),
+    (FourCC::YVYU, packed_yuv(true, true)),
+    (FourCC::VYUY, packed_yuv(true, false)),
+    (FourCC::GREY, FourCCLayout::Gray),
+    (FourCC::Y800, FourCCLayout::Gray),
+    (FourCC::Y8, FourCCLayout::Gray),
+    (FourCC::Y8_NUL, FourCCLayout::Gray),
+    (FourCC::RGB1, packed_rgb(1)),
+    (FourCC::BGR1, packed_rgb(1)),
+    (FourCC::RGB4, packed_rgb(4)),
+    (FourCC::BGR4, packed_rgb(4)),
+    (FourCC::BI_BITFIELDS, packed_rgb(4)),
+    (FourCC::RGB3, packed_rgb(3)),
+    (FourCC::BGR3, packed_rgb(3)),
+    (FourCC::RGBP, packed_rgb(2)),
+    (FourCC::RGBO, packed_rgb(2)),
+    (FourCC::RGBR, packed_rgb(2)),
+    (FourCC::RGBQ, packed_rgb(2)),
+    (FourCC::R444, packed_rgb(2)),
+    (FourCC::JPEG, FourCCLayout::Jpeg),
+    (FourCC::MJPG, FourCCLayout::Jpeg),
+];
+
+/// Check whether `actual` bytes of data are exactly what an image of the given format and size needs. JPEG data is not checked because its length is variable.
+pub(crate) fn check_data_length(
+    format: FourCC,
+    width: u32,
+    height: u32,
+    actual: usize,
+) -> Result<(), ZBarRustError> {
+    match format.data_length(width, height) {
+        Some(expected) if expected != actual => Err(ZBarRustError::BufferSizeMismatch {
+            expected,
+            actual,
+        }),
+        Some(_) => Ok(()),
+        None if format.layout() == Some(FourCCLayout::Jpeg) => Ok(()),
+        None => Err(ZBarRustError::UnsupportedFormat(format)),
+    }
+}
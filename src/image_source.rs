@@ -0,0 +1,179 @@
+use std::ops::Deref;
+
+use image::{DynamicImage, GenericImageView, ImageBuffer, Luma, LumaA, Rgb, Rgba, SubImage};
+
+use crate::{convert, FourCC, ZBarImageScanResult, ZBarImageScanner, ZBarRegion, ZBarRustError};
+
+/// Images of the `image` crate which can be scanned directly by `ZBarImageScanner::scan_source`.
+///
+/// Grayscale images are scanned without being copied. Other images are converted to grayscale first, and sub-views only have their own pixels converted. The points of the results are always in the coordinates of the parent image.
+pub trait ZBarImageSource {
+    /// Scan this image with `scanner`.
+    fn scan_with(
+        &self,
+        scanner: &mut ZBarImageScanner,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError>;
+}
+
+macro_rules! impl_image_buffer {
+    ($pixel:ty, $channels:expr) => {
+        impl<C: Deref<Target = [u8]>> ZBarImageSource for ImageBuffer<$pixel, C> {
+            fn scan_with(
+                &self,
+                scanner: &mut ZBarImageScanner,
+            ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+                let (width, height) = self.dimensions();
+
+                scan_pixels(
+                    scanner,
+                    self.as_raw(),
+                    width,
+                    height,
+                    $channels,
+                    ZBarRegion::new(0, 0, width, height),
+                )
+            }
+        }
+
+        impl<C: Deref<Target = [u8]>> ZBarImageSource for SubImage<&ImageBuffer<$pixel, C>> {
+            fn scan_with(
+                &self,
+                scanner: &mut ZBarImageScanner,
+            ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+                let parent = self.inner();
+
+                scan_pixels(
+                    scanner,
+                    parent.as_raw(),
+                    parent.width(),
+                    parent.height(),
+                    $channels,
+                    sub_image_region(self),
+                )
+            }
+        }
+    };
+}
+
+impl_image_buffer!(Luma<u8>, 1);
+impl_image_buffer!(LumaA<u8>, 2);
+impl_image_buffer!(Rgb<u8>, 3);
+impl_image_buffer!(Rgba<u8>, 4);
+
+impl ZBarImageSource for DynamicImage {
+    fn scan_with(
+        &self,
+        scanner: &mut ZBarImageScanner,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        let (width, height) = self.dimensions();
+
+        scan_dynamic_image(scanner, self, ZBarRegion::new(0, 0, width, height))
+    }
+}
+
+impl ZBarImageSource for SubImage<&DynamicImage> {
+    fn scan_with(
+        &self,
+        scanner: &mut ZBarImageScanner,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        scan_dynamic_image(scanner, self.inner(), sub_image_region(self))
+    }
+}
+
+#[inline]
+fn sub_image_region<I: Deref>(image: &SubImage<I>) -> ZBarRegion
+where
+    I::Target: GenericImageView, {
+    let (x, y) = image.offsets();
+    let (width, height) = image.dimensions();
+
+    ZBarRegion::new(x, y, width, height)
+}
+
+fn scan_dynamic_image(
+    scanner: &mut ZBarImageScanner,
+    image: &DynamicImage,
+    region: ZBarRegion,
+) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+    let (width, height) = image.dimensions();
+
+    match image {
+        DynamicImage::ImageLuma8(image) => {
+            scan_pixels(scanner, image.as_raw(), width, height, 1, region)
+        },
+        DynamicImage::ImageLumaA8(image) => {
+            scan_pixels(scanner, image.as_raw(), width, height, 2, region)
+        },
+        DynamicImage::ImageRgb8(image) => {
+            scan_pixels(scanner, image.as_raw(), width, height, 3, region)
+        },
+        DynamicImage::ImageRgba8(image) => {
+            scan_pixels(scanner, image.as_raw(), width, height, 4, region)
+        },
+        // images with more than 8 bits per sample have to be converted as a whole
+        _ => scan_pixels(scanner, image.to_luma8().as_raw(), width, height, 1, region),
+    }
+}
+
+/// Scan a region of an image of 8-bit pixels with `channels` samples each, ordered as Y, Y A, R G B or R G B A.
+fn scan_pixels(
+    scanner: &mut ZBarImageScanner,
+    data: &[u8],
+    width: u32,
+    height: u32,
+    channels: usize,
+    region: ZBarRegion,
+) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+    if !region.is_within(width, height) {
+        return Err(ZBarRustError::RegionOutOfBounds {
+            region,
+            width,
+            height,
+        });
+    }
+
+    // the buffer of an `ImageBuffer` can be longer than its pixels
+    let data = &data[..(width as usize * height as usize * channels).min(data.len())];
+
+    if channels == 1 {
+        // ZBar keeps the coordinates of the whole image when scanning a cropped image
+        return scanner.scan_region(data, width, height, FourCC::Y800, region);
+    }
+
+    let row_bytes = width as usize * channels;
+    let mut luma = Vec::with_capacity(region.width as usize * region.height as usize);
+
+    if row_bytes > 0 {
+        let start = region.x as usize * channels;
+        let end = start + region.width as usize * channels;
+
+        for row in data.chunks_exact(row_bytes).skip(region.y as usize).take(region.height as usize)
+        {
+            let row = &row[start..end];
+
+            match channels {
+                2 => luma.extend(row.iter().step_by(2)),
+                _ => luma.extend(convert::packed_rgb_to_luma(row, channels, [0, 1, 2])),
+            }
+        }
+    }
+
+    let mut results = scanner.scan_y800(luma, region.width, region.height)?;
+
+    for result in results.iter_mut() {
+        offset_points(result, region.x as i32, region.y as i32);
+    }
+
+    Ok(results)
+}
+
+fn offset_points(result: &mut ZBarImageScanResult, x: i32, y: i32) {
+    for point in result.points.iter_mut() {
+        point.0 += x;
+        point.1 += y;
+    }
+
+    for component in result.components.iter_mut() {
+        offset_points(component, x, y);
+    }
+}
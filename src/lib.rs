@@ -8,6 +8,9 @@ To compile this crate, you need to compile the ZBar library first. You can insta
 * `ZBAR_LIB_DIRS`: The directories of library files, like `-L`. Use `:` to separate.
 * `ZBAR_LIBS`: The library names that you want to link, like `-l`. Use `:` to separate. Typically, it is **iconv:zbar**.
 * `ZBAR_INCLUDE_DIRS`: The directories of header files, like `-i`. Use `:` to separate.
+* `ZBAR_VERSION`: The version of the ZBar library, like `0.23.93`, in case it cannot be found out from its header file or pkg-config. APIs which older versions lack are only available when the version is new enough.
+
+The detected version is available as `COMPILED_VERSION`. Call `check_version` at startup to make sure the ZBar library loaded at runtime fits it.
 
 ## Examples
 
@@ -29,13 +32,91 @@ for result in results {
 }
 ```
 
+## Raw Bindings
+
+The `sys` module binds all the functions of `zbar.h`, along with the values of its enums. The functions which used to be declared at the crate root are still re-exported there.
+
+## Features
+
+* `image`: Scan `DynamicImage`, `GrayImage`, `RgbImage`, `RgbaImage` and their `SubImage` views of the [image](https://crates.io/crates/image) crate directly, with `ZBarImageScanner::scan_source`.
+* `serde`: Serialize and deserialize `ScannerConfig` as config strings.
+* `dynamic-loading`: Load the ZBar library (`libzbar.so.0` on Linux) at runtime instead of linking it, so that programs can start on systems without ZBar. The safe API returns a `ZBarRustError::LibraryUnavailable` error when the library cannot be loaded, or a `ZBarRustError::VersionMismatch` error when it lacks APIs of the version this crate was compiled against, and `load_library` checks that in advance. The ZBar library does not have to be installed at build time, but its version is still detected from `ZBAR_VERSION`, `ZBAR_INCLUDE_DIRS` or pkg-config, so `ZBAR_VERSION` has to be set when ZBar is not installed. It cannot be used with `vendored`.
+* `vendored`: Compile ZBar from source and link it statically, instead of linking the system library. The sources are not bundled with this crate, so the `ZBAR_SRC_DIR` environment variable has to point to a checkout of [ZBar](https://github.com/mchehab/zbar) 0.23. ZBar is licensed under the LGPL-2.1-or-later, which applies to programs statically linking it. Only the image scanner is built, so the processor, video and window functions are not available. All the symbologies are built, unless the build is trimmed to some of them with the `vendored-ean`, `vendored-i25`, `vendored-databar`, `vendored-codabar`, `vendored-code39`, `vendored-code93`, `vendored-code128`, `vendored-pdf417`, `vendored-qrcode` and `vendored-sqcode` features, which also enable `vendored`. `vendored-all` is the same as `vendored`.
+
+```rust,ignore
+let img = image::open(INPUT_IMAGE_PATH).unwrap();
+
+let mut scanner = ZBarImageScanner::new();
+
+let results = scanner.scan_source(&img).unwrap();
+```
+
 More examples are in the `examples` folder.
 */
 
-use std::{ptr, slice};
-
+mod builder;
+mod config;
+mod convert;
+mod decoder;
+#[cfg(feature = "dynamic-loading")]
+mod dynamic;
+mod errors;
+mod fourcc;
+#[cfg(feature = "image")]
+mod image_source;
+#[cfg(not(feature = "vendored"))]
+mod processor;
+mod scanner;
+pub mod sys;
+mod version;
+
+use std::{
+    any::Any,
+    borrow::Cow,
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+
+pub use builder::*;
+pub use config::*;
+pub use decoder::*;
+#[cfg(feature = "dynamic-loading")]
+pub use dynamic::*;
 use enum_ordinalize::Ordinalize;
-use libc::{c_char, c_int, c_uint, c_ulong, c_void};
+pub use errors::*;
+pub use fourcc::*;
+#[cfg(feature = "image")]
+pub use image_source::*;
+use libc::{c_int, c_uint, c_ulong, c_void};
+#[cfg(not(feature = "vendored"))]
+pub use processor::*;
+pub use scanner::*;
+#[cfg(zbar_0_22)]
+#[doc(no_inline)]
+pub use sys::zbar_image_scanner_get_config;
+// the functions which were declared here before the `sys` module
+#[doc(no_inline)]
+pub use sys::{
+    zbar_image_convert, zbar_image_convert_resize, zbar_image_create, zbar_image_destroy,
+    zbar_image_first_symbol, zbar_image_free_data, zbar_image_get_crop, zbar_image_get_data,
+    zbar_image_get_data_length, zbar_image_get_format, zbar_image_get_height,
+    zbar_image_get_sequence, zbar_image_get_size, zbar_image_get_symbols, zbar_image_get_userdata,
+    zbar_image_get_width, zbar_image_read, zbar_image_ref, zbar_image_scanner_create,
+    zbar_image_scanner_destroy, zbar_image_scanner_enable_cache, zbar_image_scanner_get_results,
+    zbar_image_scanner_recycle_image, zbar_image_scanner_set_config,
+    zbar_image_scanner_set_data_handler, zbar_image_set_crop, zbar_image_set_data,
+    zbar_image_set_format, zbar_image_set_sequence, zbar_image_set_size, zbar_image_set_symbols,
+    zbar_image_set_userdata, zbar_image_write, zbar_parse_config, zbar_scan_image,
+    zbar_set_verbosity, zbar_symbol_first_component, zbar_symbol_get_components,
+    zbar_symbol_get_configs, zbar_symbol_get_count, zbar_symbol_get_data,
+    zbar_symbol_get_data_length, zbar_symbol_get_loc_size, zbar_symbol_get_loc_x,
+    zbar_symbol_get_loc_y, zbar_symbol_get_modifiers, zbar_symbol_get_orientation,
+    zbar_symbol_get_quality, zbar_symbol_get_type, zbar_symbol_next, zbar_symbol_ref,
+    zbar_symbol_set_first_symbol, zbar_symbol_set_first_unfiltered, zbar_symbol_set_get_size,
+    zbar_symbol_set_ref, zbar_symbol_xml, zbar_version,
+};
+pub use version::*;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ordinalize)]
 #[repr(isize)]
@@ -44,33 +125,158 @@ pub enum ZBarColor {
     ZBarBar   = 1,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Ordinalize)]
-#[repr(isize)]
+/// The type of a symbol, or a symbology to configure.
+///
+/// Values which this crate does not know, for example symbologies of a newer ZBar, are kept as `Unknown`. `ZBarSQCode` and `ZBarCode32` are only available with ZBar 0.23 or later.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum ZBarSymbolType {
-    ZBarNone       = 0,
-    ZBarPartial    = 1,
-    ZBarEAN2       = 2,
-    ZBarEAN5       = 5,
-    ZBarEAN8       = 8,
-    ZBarUPCE       = 9,
-    ZBarISBN10     = 10,
-    ZBarUPCA       = 12,
-    ZBarEAN13      = 13,
-    ZBarISBN13     = 14,
-    ZBarComposite  = 15,
-    ZBarI25        = 25,
-    ZBarDataBar    = 34,
-    ZBarDataBarExp = 35,
-    ZBarCodeBar    = 38,
-    ZBarCode39     = 39,
-    ZBarPDF417     = 57,
-    ZBarQRCode     = 64,
-    ZBarCode93     = 93,
-    ZBarCode128    = 128,
-    ZBarSymbol     = 0x00FF,
-    ZBarAddOn2     = 0x0200,
-    ZBarAddOn5     = 0x0500,
-    ZBarAddOn      = 0x0700,
+    ZBarNone,
+    ZBarPartial,
+    ZBarEAN2,
+    ZBarEAN5,
+    ZBarEAN8,
+    ZBarUPCE,
+    ZBarISBN10,
+    ZBarUPCA,
+    ZBarEAN13,
+    ZBarISBN13,
+    ZBarComposite,
+    ZBarI25,
+    ZBarDataBar,
+    ZBarDataBarExp,
+    ZBarCodeBar,
+    ZBarCode39,
+    ZBarPDF417,
+    ZBarQRCode,
+    #[cfg(zbar_0_23)]
+    ZBarSQCode,
+    ZBarCode93,
+    ZBarCode128,
+    #[cfg(zbar_0_23)]
+    ZBarCode32,
+    ZBarSymbol,
+    ZBarAddOn2,
+    ZBarAddOn5,
+    ZBarAddOn,
+    Unknown(i32),
+}
+
+impl ZBarSymbolType {
+    /// All the known symbol types.
+    pub const VARIANTS: &'static [ZBarSymbolType] = &[
+        ZBarSymbolType::ZBarNone,
+        ZBarSymbolType::ZBarPartial,
+        ZBarSymbolType::ZBarEAN2,
+        ZBarSymbolType::ZBarEAN5,
+        ZBarSymbolType::ZBarEAN8,
+        ZBarSymbolType::ZBarUPCE,
+        ZBarSymbolType::ZBarISBN10,
+        ZBarSymbolType::ZBarUPCA,
+        ZBarSymbolType::ZBarEAN13,
+        ZBarSymbolType::ZBarISBN13,
+        ZBarSymbolType::ZBarComposite,
+        ZBarSymbolType::ZBarI25,
+        ZBarSymbolType::ZBarDataBar,
+        ZBarSymbolType::ZBarDataBarExp,
+        ZBarSymbolType::ZBarCodeBar,
+        ZBarSymbolType::ZBarCode39,
+        ZBarSymbolType::ZBarPDF417,
+        ZBarSymbolType::ZBarQRCode,
+        #[cfg(zbar_0_23)]
+        ZBarSymbolType::ZBarSQCode,
+        ZBarSymbolType::ZBarCode93,
+        ZBarSymbolType::ZBarCode128,
+        #[cfg(zbar_0_23)]
+        ZBarSymbolType::ZBarCode32,
+        ZBarSymbolType::ZBarSymbol,
+        ZBarSymbolType::ZBarAddOn2,
+        ZBarSymbolType::ZBarAddOn5,
+        ZBarSymbolType::ZBarAddOn,
+    ];
+
+    /// Convert a `zbar_symbol_type_t` value. Values which this crate does not know become `Unknown`.
+    pub const fn from_raw(value: i32) -> ZBarSymbolType {
+        match value {
+            0 => ZBarSymbolType::ZBarNone,
+            1 => ZBarSymbolType::ZBarPartial,
+            2 => ZBarSymbolType::ZBarEAN2,
+            5 => ZBarSymbolType::ZBarEAN5,
+            8 => ZBarSymbolType::ZBarEAN8,
+            9 => ZBarSymbolType::ZBarUPCE,
+            10 => ZBarSymbolType::ZBarISBN10,
+            12 => ZBarSymbolType::ZBarUPCA,
+            13 => ZBarSymbolType::ZBarEAN13,
+            14 => ZBarSymbolType::ZBarISBN13,
+            15 => ZBarSymbolType::ZBarComposite,
+            25 => ZBarSymbolType::ZBarI25,
+            34 => ZBarSymbolType::ZBarDataBar,
+            35 => ZBarSymbolType::ZBarDataBarExp,
+            38 => ZBarSymbolType::ZBarCodeBar,
+            39 => ZBarSymbolType::ZBarCode39,
+            57 => ZBarSymbolType::ZBarPDF417,
+            64 => ZBarSymbolType::ZBarQRCode,
+            #[cfg(zbar_0_23)]
+            80 => ZBarSymbolType::ZBarSQCode,
+            93 => ZBarSymbolType::ZBarCode93,
+            128 => ZBarSymbolType::ZBarCode128,
+            #[cfg(zbar_0_23)]
+            129 => ZBarSymbolType::ZBarCode32,
+            0x00FF => ZBarSymbolType::ZBarSymbol,
+            0x0200 => ZBarSymbolType::ZBarAddOn2,
+            0x0500 => ZBarSymbolType::ZBarAddOn5,
+            0x0700 => ZBarSymbolType::ZBarAddOn,
+            _ => ZBarSymbolType::Unknown(value),
+        }
+    }
+
+    /// Convert to a `zbar_symbol_type_t` value.
+    pub const fn to_raw(self) -> i32 {
+        match self {
+            ZBarSymbolType::ZBarNone => 0,
+            ZBarSymbolType::ZBarPartial => 1,
+            ZBarSymbolType::ZBarEAN2 => 2,
+            ZBarSymbolType::ZBarEAN5 => 5,
+            ZBarSymbolType::ZBarEAN8 => 8,
+            ZBarSymbolType::ZBarUPCE => 9,
+            ZBarSymbolType::ZBarISBN10 => 10,
+            ZBarSymbolType::ZBarUPCA => 12,
+            ZBarSymbolType::ZBarEAN13 => 13,
+            ZBarSymbolType::ZBarISBN13 => 14,
+            ZBarSymbolType::ZBarComposite => 15,
+            ZBarSymbolType::ZBarI25 => 25,
+            ZBarSymbolType::ZBarDataBar => 34,
+            ZBarSymbolType::ZBarDataBarExp => 35,
+            ZBarSymbolType::ZBarCodeBar => 38,
+            ZBarSymbolType::ZBarCode39 => 39,
+            ZBarSymbolType::ZBarPDF417 => 57,
+            ZBarSymbolType::ZBarQRCode => 64,
+            #[cfg(zbar_0_23)]
+            ZBarSymbolType::ZBarSQCode => 80,
+            ZBarSymbolType::ZBarCode93 => 93,
+            ZBarSymbolType::ZBarCode128 => 128,
+            #[cfg(zbar_0_23)]
+            ZBarSymbolType::ZBarCode32 => 129,
+            ZBarSymbolType::ZBarSymbol => 0x00FF,
+            ZBarSymbolType::ZBarAddOn2 => 0x0200,
+            ZBarSymbolType::ZBarAddOn5 => 0x0500,
+            ZBarSymbolType::ZBarAddOn => 0x0700,
+            ZBarSymbolType::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<i32> for ZBarSymbolType {
+    #[inline]
+    fn from(value: i32) -> Self {
+        ZBarSymbolType::from_raw(value)
+    }
+}
+
+impl From<ZBarSymbolType> for i32 {
+    #[inline]
+    fn from(symbol_type: ZBarSymbolType) -> Self {
+        symbol_type.to_raw()
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ordinalize)]
@@ -101,19 +307,31 @@ pub enum ZBarError {
     ZBarErrNum,
 }
 
+/// The configs of ZBar, with the values of the linked version. `ZBarCfgBinary` and `ZBarCfgTestInverted` are only available with ZBar 0.23 or later, which also moves `ZBarCfgNum`.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ordinalize)]
 #[repr(isize)]
 pub enum ZBarConfig {
-    ZBarCfgEnable    = 0,
-    ZBarCfgAddCheck  = 1,
-    ZBarCfgEmitCheck = 2,
-    ZBarCfgASCII     = 3,
-    ZBarCfgNum       = 4,
-    ZBarCfgMinLen    = 0x20,
-    ZBarCfgMaxLen    = 0x21,
-    ZBarCfgPosition  = 0x80,
-    ZBarCfgXDensity  = 0x100,
-    ZBarCfgYDensity  = 0x101,
+    ZBarCfgEnable       = 0,
+    ZBarCfgAddCheck     = 1,
+    ZBarCfgEmitCheck    = 2,
+    ZBarCfgASCII        = 3,
+    /// Keep the data of QR codes as raw bytes, instead of converting it to UTF-8.
+    #[cfg(zbar_0_23)]
+    ZBarCfgBinary       = 4,
+    #[cfg(zbar_0_23)]
+    ZBarCfgNum          = 5,
+    #[cfg(not(zbar_0_23))]
+    ZBarCfgNum          = 4,
+    ZBarCfgMinLen       = 0x20,
+    ZBarCfgMaxLen       = 0x21,
+    /// How many times a symbol has to be decoded the same way before it is reported.
+    ZBarCfgUncertainty  = 0x40,
+    ZBarCfgPosition     = 0x80,
+    /// Also scan the image with its colors inverted, for light codes on dark backgrounds.
+    #[cfg(zbar_0_23)]
+    ZBarCfgTestInverted = 0x81,
+    ZBarCfgXDensity     = 0x100,
+    ZBarCfgYDensity     = 0x101,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ordinalize)]
@@ -124,6 +342,83 @@ pub enum ZBarModifier {
     ZBarModNum,
 }
 
+/// A set of boolean `ZBarConfig`s, such as the configs a symbol was decoded with.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct ZBarConfigSet {
+    bits: u32,
+}
+
+impl ZBarConfigSet {
+    /// Create a set from a bitmask where bit `n` stands for the config whose ordinal is `n`.
+    #[inline]
+    pub const fn from_bits(bits: u32) -> ZBarConfigSet {
+        ZBarConfigSet {
+            bits,
+        }
+    }
+
+    #[inline]
+    pub const fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Check whether the set contains `config`. Only boolean configs (those before `ZBarCfgNum`) can be contained.
+    #[inline]
+    pub fn contains(&self, config: ZBarConfig) -> bool {
+        let ordinal = config.ordinal();
+
+        ordinal < ZBarConfig::ZBarCfgNum.ordinal() && self.bits & (1 << ordinal) != 0
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = ZBarConfig> + '_ {
+        ZBarConfig::VARIANTS.iter().copied().filter(|config| self.contains(*config))
+    }
+}
+
+/// A set of `ZBarModifier`s.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct ZBarModifierSet {
+    bits: u32,
+}
+
+impl ZBarModifierSet {
+    /// Create a set from a bitmask where bit `n` stands for the modifier whose ordinal is `n`.
+    #[inline]
+    pub const fn from_bits(bits: u32) -> ZBarModifierSet {
+        ZBarModifierSet {
+            bits,
+        }
+    }
+
+    #[inline]
+    pub const fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    #[inline]
+    pub fn contains(&self, modifier: ZBarModifier) -> bool {
+        let ordinal = modifier.ordinal();
+
+        ordinal < ZBarModifier::ZBarModNum.ordinal() && self.bits & (1 << ordinal) != 0
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = ZBarModifier> + '_ {
+        ZBarModifier::VARIANTS.iter().copied().filter(|modifier| self.contains(*modifier))
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ordinalize)]
 #[repr(isize)]
 pub enum VideoControlType {
@@ -135,322 +430,1192 @@ pub enum VideoControlType {
     VideoCntlBoolean   = 6,
 }
 
-// TODO: ----- General Interface START-----
+// TODO: ----- Image Interface START-----
 
-#[link(name = "zbar")]
-extern "C" {
-    pub fn zbar_version(major: *mut c_uint, minor: *mut c_uint, patch: *mut c_uint) -> c_int;
-    pub fn zbar_set_verbosity(verbosity: c_int);
+/// A rectangular region of an image, in pixels.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct ZBarRegion {
+    pub x:      u32,
+    pub y:      u32,
+    pub width:  u32,
+    pub height: u32,
 }
 
-// TODO: ----- General Interface END-----
+impl ZBarRegion {
+    #[inline]
+    pub const fn new(x: u32, y: u32, width: u32, height: u32) -> ZBarRegion {
+        ZBarRegion {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
 
-// TODO: ----- Image Interface START-----
+    /// Check whether this region lies within an image of the given size.
+    #[inline]
+    pub fn is_within(&self, width: u32, height: u32) -> bool {
+        self.x as u64 + self.width as u64 <= width as u64
+            && self.y as u64 + self.height as u64 <= height as u64
+    }
+}
 
-#[link(name = "zbar")]
-extern "C" {
-    pub fn zbar_image_create() -> *mut c_void;
-    pub fn zbar_image_destroy(image: *mut c_void);
-    pub fn zbar_image_ref(image: *mut c_void, refs: c_int);
-    pub fn zbar_image_convert(image: *const c_void, format: c_ulong) -> *mut c_void;
-    pub fn zbar_image_convert_resize(
-        image: *const c_void,
-        format: c_ulong,
-        width: c_uint,
-        height: c_int,
-    ) -> *mut c_void;
-    pub fn zbar_image_get_format(image: *const c_void) -> c_ulong;
-    pub fn zbar_image_get_sequence(image: *const c_void) -> c_uint;
-    pub fn zbar_image_get_width(image: *const c_void) -> c_uint;
-    pub fn zbar_image_get_height(image: *const c_void) -> c_uint;
-    pub fn zbar_image_get_size(image: *const c_void, width: *mut c_uint, height: *mut c_uint);
-    pub fn zbar_image_get_crop(
-        image: *const c_void,
-        x: *mut c_uint,
-        y: *mut c_uint,
-        width: *mut c_uint,
-        height: *mut c_uint,
-    );
-    pub fn zbar_image_get_data(image: *const c_void) -> *const c_void;
-    pub fn zbar_image_get_data_length(image: *const c_void) -> c_ulong;
-    pub fn zbar_image_get_symbols(image: *const c_void) -> *const c_void;
-    pub fn zbar_image_set_symbols(image: *mut c_void, symbols: *const c_void);
-    pub fn zbar_image_first_symbol(image: *const c_void) -> *const c_void;
-    pub fn zbar_image_set_format(image: *mut c_void, format: c_ulong);
-    pub fn zbar_image_set_sequence(image: *mut c_void, sequence_num: c_ulong);
-    pub fn zbar_image_set_size(image: *mut c_void, width: c_ulong, height: c_ulong);
-    pub fn zbar_image_set_crop(
-        image: *mut c_void,
-        x: c_ulong,
-        y: c_ulong,
-        width: c_ulong,
-        height: c_ulong,
-    );
-    pub fn zbar_image_set_data(
-        image: *mut c_void,
-        data: *const c_void,
-        data_byte_length: c_ulong,
-        handler: *mut c_void,
-    );
-    pub fn zbar_image_free_data(image: *mut c_void);
-    pub fn zbar_image_set_userdata(image: *mut c_void, userdata: *const c_void);
-    pub fn zbar_image_get_userdata(image: *const c_void) -> *const c_void;
-    pub fn zbar_image_write(image: *const c_void, filebase: *const c_char) -> c_uint;
-    pub fn zbar_image_read(filename: *mut c_char) -> *const c_void;
+/// An image. The lifetime `'a` is the lifetime of borrowed pixel data, which is `'static` if the image owns its data or has none.
+pub struct ZBarImage<'a> {
+    image:    *mut c_void,
+    _phantom: PhantomData<&'a [u8]>,
 }
 
-pub struct ZBarImage {
-    image: *mut c_void,
+/// The cleanup handler of images created by `ZBarImage::from_owned`.
+unsafe extern "C" fn zbar_image_free_owned_data(image: *mut c_void) {
+    let data = zbar_image_get_data(image) as *mut u8;
+
+    if !data.is_null() {
+        let data_length = zbar_image_get_data_length(image) as usize;
+
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(data, data_length)));
+    }
 }
 
-fn zbar_image_free_data_do_nothing(_image: *mut c_void) {}
+impl<'a> ZBarImage<'a> {
+    /// Create an empty image.
+    ///
+    /// # Panics
+    ///
+    /// Panics if ZBar cannot allocate the image. Use `try_new` to handle that case.
+    pub fn new() -> ZBarImage<'a> {
+        ZBarImage::try_new().unwrap()
+    }
+
+    /// Create an empty image.
+    pub fn try_new() -> Result<ZBarImage<'a>, ZBarRustError> {
+        #[cfg(feature = "dynamic-loading")]
+        load_library()?;
 
-impl ZBarImage {
-    pub fn new() -> ZBarImage {
         let image = unsafe { zbar_image_create() };
 
-        ZBarImage {
-            image,
+        if image.is_null() {
+            return Err(ZBarRustError::NullHandle("zbar_image_create"));
         }
+
+        Ok(ZBarImage {
+            image,
+            _phantom: PhantomData,
+        })
     }
 
-    pub fn set_format(&mut self, format: u32) {
+    /// Create an image which borrows its pixel data. The length of `data` must match the size that `width`, `height` and `format` imply.
+    pub fn from_borrowed(
+        data: &'a [u8],
+        width: u32,
+        height: u32,
+        format: FourCC,
+    ) -> Result<ZBarImage<'a>, ZBarRustError> {
+        fourcc::check_data_length(format, width, height, data.len())?;
+
+        let mut image = ZBarImage::try_new()?;
+
+        image.set_size(width, height)?;
+        image.set_format(format)?;
+
         unsafe {
-            zbar_image_set_format(self.image, c_ulong::from(format));
+            // `data` is borrowed - do not attempt to free it
+            zbar_image_set_data(
+                image.image,
+                data.as_ptr() as *const c_void,
+                data.len() as c_ulong,
+                None,
+            );
         }
+
+        Ok(image)
     }
 
-    pub fn set_size(&mut self, width: u32, height: u32) {
-        unsafe {
-            zbar_image_set_size(self.image, c_ulong::from(width), c_ulong::from(height));
+    /// Create an image from pixel data whose rows are padded, such as a frame buffer. `planes` holds the `(offset, stride)` of every plane of `format`, in the order of `FourCC::planes`: the byte where the plane starts, and the number of bytes from the start of a row to the start of the next one. Planes can be aligned, like the chroma planes of a frame whose luma plane is allocated with more rows than the image has.
+    ///
+    /// The data is borrowed if its planes follow each other without padding, otherwise it is copied into tightly packed rows.
+    pub fn from_borrowed_strided(
+        data: &'a [u8],
+        width: u32,
+        height: u32,
+        format: FourCC,
+        planes: &[(usize, usize)],
+    ) -> Result<ZBarImage<'a>, ZBarRustError> {
+        match convert::pack_rows(data, format, width, height, planes, false)? {
+            Cow::Borrowed(data) => ZBarImage::from_borrowed(data, width, height, format),
+            Cow::Owned(data) => ZBarImage::from_owned(data, width, height, format),
         }
     }
 
-    pub fn set_ref(&mut self, r: isize) {
+    /// Set the pixel format. It fails if the attached data does not fit the new format.
+    pub fn set_format(&mut self, format: FourCC) -> Result<(), ZBarRustError> {
+        self.check_data_length(format, self.width(), self.height())?;
+
         unsafe {
-            zbar_image_ref(self.image, r as c_int);
+            zbar_image_set_format(self.image, c_ulong::from(format.to_u32()));
         }
+
+        Ok(())
     }
 
-    pub fn destroy(&mut self) {
-        if !self.image.is_null() {
-            unsafe {
-                zbar_image_destroy(self.image);
-                self.image = ptr::null_mut();
-            }
+    /// Set the size of the image. It fails if the attached data does not fit the new size.
+    pub fn set_size(&mut self, width: u32, height: u32) -> Result<(), ZBarRustError> {
+        self.check_data_length(self.format(), width, height)?;
+
+        unsafe {
+            zbar_image_set_size(self.image, width as c_uint, height as c_uint);
         }
-    }
-}
 
-impl Default for ZBarImage {
-    #[inline]
-    fn default() -> Self {
-        ZBarImage::new()
+        Ok(())
     }
-}
 
-impl Drop for ZBarImage {
-    fn drop(&mut self) {
-        self.destroy();
-    }
-}
+    /// Convert the image to another pixel format with ZBar's converter. The new image owns its data.
+    pub fn convert(&self, format: FourCC) -> Result<ZBarImage<'static>, ZBarRustError> {
+        self.check_has_data()?;
 
-// TODO: ----- Image Interface END-----
+        let image = unsafe { zbar_image_convert(self.image, c_ulong::from(format.to_u32())) };
 
-// TODO: ----- Symbol Interface START-----
+        self.to_converted(image, format)
+    }
 
-#[link(name = "zbar")]
-extern "C" {
-    pub fn zbar_symbol_ref(symbol: *const c_void, refs: c_int);
-    pub fn zbar_symbol_get_type(symbol: *const c_void) -> c_int;
-    pub fn zbar_symbol_get_configs(symbol: *const c_void) -> c_uint;
-    pub fn zbar_symbol_get_modifiers(symbol: *const c_void) -> c_uint;
-    pub fn zbar_symbol_get_data(symbol: *const c_void) -> *mut c_char;
-    pub fn zbar_symbol_get_data_length(symbol: *const c_void) -> c_uint;
-    pub fn zbar_symbol_get_quality(symbol: *const c_void) -> c_int;
-    pub fn zbar_symbol_get_count(symbol: *const c_void) -> c_int;
-    pub fn zbar_symbol_get_loc_size(symbol: *const c_void) -> c_uint;
-    pub fn zbar_symbol_get_loc_x(symbol: *const c_void, index: c_uint) -> c_int;
-    pub fn zbar_symbol_get_loc_y(symbol: *const c_void, index: c_uint) -> c_int;
-    pub fn zbar_symbol_get_orientation(symbol: *const c_void) -> c_int;
-    pub fn zbar_symbol_next(symbol: *const c_void) -> *const c_void;
-    pub fn zbar_symbol_get_components(symbol: *const c_void) -> *const c_void;
-    pub fn zbar_symbol_first_component(symbol: *const c_void) -> *const c_void;
-    pub fn zbar_symbol_xml(
-        symbol: *const c_void,
-        buffer: *mut *mut c_char,
-        buflen: *mut c_uint,
-    ) -> *mut c_char;
-}
+    /// Convert the image to another pixel format and size with ZBar's converter. The image is not scaled, but cropped or padded to the new size. The new image owns its data.
+    pub fn convert_resize(
+        &self,
+        format: FourCC,
+        width: u32,
+        height: u32,
+    ) -> Result<ZBarImage<'static>, ZBarRustError> {
+        self.check_has_data()?;
 
-// TODO: ----- Symbol Interface END-----
+        let image = unsafe {
+            zbar_image_convert_resize(self.image, c_ulong::from(format.to_u32()), width, height)
+        };
 
-// TODO: ----- Image Scanner Interface START-----
+        self.to_converted(image, format)
+    }
 
-#[link(name = "zbar")]
-extern "C" {
-    pub fn zbar_image_scanner_create() -> *mut c_void;
-    pub fn zbar_image_scanner_destroy(scanner: *mut c_void);
-    pub fn zbar_image_scanner_set_data_handler(
-        scanner: *mut c_void,
-        handler: *const c_void,
-        userdata: *const c_void,
-    );
-    pub fn zbar_image_scanner_set_config(
-        scanner: *mut c_void,
-        symbology: c_int,
-        config: c_int,
-        value: c_int,
-    ) -> c_int;
-    pub fn zbar_image_scanner_parse_config(
-        scanner: *mut c_void,
-        config_string: *const c_char,
-    ) -> c_int;
-    pub fn zbar_image_scanner_enable_cache(scanner: *mut c_void, enable: c_int);
-    pub fn zbar_image_scanner_recycle_image(scanner: *mut c_void, image: *mut c_void);
-    pub fn zbar_image_scanner_get_results(scanner: *const c_void) -> *const c_void;
-    pub fn zbar_scan_image(scanner: *mut c_void, image: *mut c_void) -> c_int;
-}
+    /// Take the image returned by ZBar's converter, and give it data of its own.
+    fn to_converted(
+        &self,
+        image: *mut c_void,
+        format: FourCC,
+    ) -> Result<ZBarImage<'static>, ZBarRustError> {
+        if image.is_null() {
+            return Err(ZBarRustError::UnsupportedFormat(format));
+        }
 
-#[derive(Debug)]
-pub struct ZBarImageScanResult {
-    pub symbol_type: ZBarSymbolType,
-    pub data:        Vec<u8>,
-    pub points:      Vec<(i32, i32)>,
-}
+        let converted = ZBarImage {
+            image,
+            _phantom: PhantomData,
+        };
 
-pub struct ZBarImageScanner {
-    scanner: *mut c_void,
-}
+        // when the pixels stay the same, such as from a planar YUV format to `Y800`, ZBar shares the data of the source instead of copying it, which may be borrowed and longer than the new format needs
+        if unsafe { zbar_image_get_data(image) == zbar_image_get_data(self.image) } {
+            let (width, height) = (converted.width(), converted.height());
 
-impl ZBarImageScanner {
-    pub fn new() -> ZBarImageScanner {
-        let scanner = unsafe { zbar_image_scanner_create() };
+            let data = converted.data();
 
-        ZBarImageScanner {
-            scanner,
+            let data_length = format
+                .data_length(width, height)
+                .map_or(data.len(), |length| length.min(data.len()));
+
+            return ZBarImage::from_owned(&data[..data_length], width, height, format);
         }
+
+        Ok(converted)
     }
 
-    pub fn set_config(
-        &mut self,
-        symbology: ZBarSymbolType,
-        config: ZBarConfig,
-        value: isize,
-    ) -> Result<(), &'static str> {
-        let result = unsafe {
-            zbar_image_scanner_set_config(
-                self.scanner,
-                symbology.ordinal() as c_int,
-                config.ordinal() as c_int,
-                value as c_int,
-            )
-        };
-        if result == 0 {
-            Ok(())
+    /// ZBar's converter and scanner read the data without checking it, so there must be some.
+    fn check_has_data(&self) -> Result<(), ZBarRustError> {
+        if unsafe { zbar_image_get_data(self.image) }.is_null() {
+            Err(ZBarRustError::NoImageData)
         } else {
-            Err("unsuccessfully")
+            Ok(())
         }
     }
 
-    pub fn destroy(mut self) {
-        unsafe {
-            zbar_image_scanner_destroy(self.scanner);
-            self.scanner = ptr::null_mut();
+    /// Make sure that the attached data, if any, would still fit the image after changing its format or size.
+    fn check_data_length(
+        &self,
+        format: FourCC,
+        width: u32,
+        height: u32,
+    ) -> Result<(), ZBarRustError> {
+        let data = unsafe { zbar_image_get_data(self.image) };
+
+        if data.is_null() {
+            return Ok(());
         }
+
+        let actual = unsafe { zbar_image_get_data_length(self.image) } as usize;
+
+        fourcc::check_data_length(format, width, height, actual)
     }
 
-    pub fn scan_y800<D: AsRef<[u8]>>(
-        &mut self,
-        data: D,
-        width: u32,
-        height: u32,
-    ) -> Result<Vec<ZBarImageScanResult>, &'static str> {
-        //        let format: u32 = unsafe { transmute([b'Y', b'8', b'0', b'0']) };
-        self.scan(data, width, height, 808_466_521)
+    #[inline]
+    pub fn width(&self) -> u32 {
+        unsafe { zbar_image_get_width(self.image) }
     }
 
-    pub fn scan_gray<D: AsRef<[u8]>>(
-        &mut self,
-        data: D,
-        width: u32,
-        height: u32,
-    ) -> Result<Vec<ZBarImageScanResult>, &'static str> {
-        //        let format: u32 = unsafe { transmute([b'G', b'R', b'A', b'Y']) };
-        self.scan(data, width, height, 1_497_453_127)
+    #[inline]
+    pub fn height(&self) -> u32 {
+        unsafe { zbar_image_get_height(self.image) }
     }
 
-    pub fn scan<D: AsRef<[u8]>>(
-        &mut self,
-        data: D,
-        width: u32,
-        height: u32,
-        format: u32,
-    ) -> Result<Vec<ZBarImageScanResult>, &'static str> {
-        let data = data.as_ref();
+    /// The pixel format.
+    #[inline]
+    pub fn format(&self) -> FourCC {
+        FourCC::from_u32(unsafe { zbar_image_get_format(self.image) } as u32)
+    }
+
+    /// The pixel data. It is empty if no data has been attached.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        unsafe {
+            let data = zbar_image_get_data(self.image);
 
-        let mut image = ZBarImage::new();
+            if data.is_null() {
+                return &[];
+            }
+
+            let data_length = zbar_image_get_data_length(self.image) as usize;
+
+            slice::from_raw_parts(data as *const u8, data_length)
+        }
+    }
 
-        image.set_size(width, height);
-        image.set_format(format);
+    /// The frame sequence number of the image.
+    #[inline]
+    pub fn sequence(&self) -> u32 {
+        unsafe { zbar_image_get_sequence(self.image) }
+    }
 
+    #[inline]
+    pub fn set_sequence(&mut self, sequence: u32) {
         unsafe {
-            zbar_image_set_data(
-                image.image,
-                data.as_ptr() as *const c_void,
-                data.len() as c_ulong,
-                zbar_image_free_data_do_nothing as *mut c_void, /* `data` is borrowed - do not attempt to free it */
+            zbar_image_set_sequence(self.image, sequence);
+        }
+    }
+
+    /// Limit scanning to a region of the image. ZBar clamps the region to the image size. The crop is reset by `set_size`, so call this method afterwards.
+    ///
+    /// Symbol locations are still reported in the coordinates of the whole image.
+    #[inline]
+    pub fn set_crop(&mut self, region: ZBarRegion) {
+        unsafe {
+            zbar_image_set_crop(self.image, region.x, region.y, region.width, region.height);
+        }
+    }
+
+    /// The region of the image to be scanned. It is the whole image if no crop has been set.
+    #[inline]
+    pub fn crop(&self) -> ZBarRegion {
+        let mut region = ZBarRegion::default();
+
+        unsafe {
+            zbar_image_get_crop(
+                self.image,
+                &mut region.x,
+                &mut region.y,
+                &mut region.width,
+                &mut region.height,
             );
         }
 
-        let n = unsafe { zbar_scan_image(self.scanner, image.image) };
+        region
+    }
 
-        if n < 0 {
-            return Err("incorrect image");
+    /// Add `r` to the reference count of the image, which is destroyed when the count drops to zero.
+    ///
+    /// # Safety
+    ///
+    /// Every reference which is added has to be released again, and no more than that. Releasing the reference of this `ZBarImage` destroys the image, and frees its data if it is owned, while this `ZBarImage` still uses it.
+    pub unsafe fn set_ref(&mut self, r: isize) {
+        zbar_image_ref(self.image, r as c_int);
+    }
+
+    /// Iterate over the symbols decoded from this image by the last scan.
+    #[inline]
+    pub fn symbols(&self) -> ZBarSymbolIter<'_> {
+        ZBarSymbolIter::new(unsafe { zbar_image_first_symbol(self.image) })
+    }
+
+    /// The symbol set decoded from this image by the last scan.
+    #[inline]
+    pub fn symbol_set(&self) -> Option<ZBarSymbolSet> {
+        unsafe { ZBarSymbolSet::from_raw(zbar_image_get_symbols(self.image)) }
+    }
+
+    /// Attach a symbol set to this image, or detach the current one with `None`. The image keeps its own reference to the set.
+    #[inline]
+    pub fn set_symbol_set(&mut self, symbols: Option<&ZBarSymbolSet>) {
+        unsafe {
+            zbar_image_set_symbols(self.image, symbols.map_or(ptr::null(), |s| s.symbols));
         }
+    }
 
-        let mut result_array = Vec::with_capacity(n as usize);
+    pub fn destroy(mut self) {
+        unsafe {
+            zbar_image_destroy(self.image);
+            self.image = ptr::null_mut();
+        }
+    }
+}
 
-        let mut symbol = unsafe { zbar_image_first_symbol(image.image) };
+impl ZBarImage<'static> {
+    /// Create an image which owns its pixel data. The data is freed when ZBar releases the image. The length of `data` must match the size that `width`, `height` and `format` imply.
+    pub fn from_owned<D: Into<Box<[u8]>>>(
+        data: D,
+        width: u32,
+        height: u32,
+        format: FourCC,
+    ) -> Result<ZBarImage<'static>, ZBarRustError> {
+        let data = data.into();
 
-        while !symbol.is_null() {
-            let symbol_type = unsafe { zbar_symbol_get_type(symbol) };
-            let symbol_type = unsafe { ZBarSymbolType::from_ordinal_unsafe(symbol_type as isize) };
+        fourcc::check_data_length(format, width, height, data.len())?;
 
-            let data = unsafe {
-                let data = zbar_symbol_get_data(symbol);
-                let data_length = zbar_symbol_get_data_length(symbol) as usize;
-                slice::from_raw_parts(data as *mut u8, data_length).to_vec()
-            };
+        let mut image = ZBarImage::try_new()?;
 
-            // extract bounding box
-            let loc_size = unsafe { zbar_symbol_get_loc_size(symbol) };
+        image.set_size(width, height)?;
+        image.set_format(format)?;
 
-            let mut points = Vec::with_capacity(loc_size as usize);
+        let data_length = data.len();
 
-            for i in 0..loc_size {
-                let x = unsafe { zbar_symbol_get_loc_x(symbol, i) };
-                let y = unsafe { zbar_symbol_get_loc_y(symbol, i) };
+        unsafe {
+            zbar_image_set_data(
+                image.image,
+                Box::into_raw(data) as *const c_void,
+                data_length as c_ulong,
+                Some(zbar_image_free_owned_data),
+            );
+        }
 
-                points.push((x, y));
-            }
+        Ok(image)
+    }
 
-            let result = ZBarImageScanResult {
-                symbol_type,
-                data,
-                points,
+    /// Create an image which owns pixel data whose rows are padded. See `from_borrowed_strided` for the meaning of `planes`.
+    ///
+    /// The data is kept as is if its planes start at the beginning and follow each other without padding, otherwise it is copied into tightly packed rows.
+    pub fn from_owned_strided<D: Into<Box<[u8]>>>(
+        data: D,
+        width: u32,
+        height: u32,
+        format: FourCC,
+        planes: &[(usize, usize)],
+    ) -> Result<ZBarImage<'static>, ZBarRustError> {
+        let data = data.into();
+
+        let (packed_length, repacked) =
+            match convert::pack_rows(&data, format, width, height, planes, false)? {
+                Cow::Borrowed(packed) if packed.as_ptr() == data.as_ptr() => (packed.len(), None),
+                Cow::Borrowed(packed) => (packed.len(), Some(packed.to_vec())),
+                Cow::Owned(packed) => (packed.len(), Some(packed)),
             };
 
-            result_array.push(result);
+        let data = match repacked {
+            Some(packed) => packed.into_boxed_slice(),
+            None => {
+                // drop the trailing bytes after the last row
+                let mut data = data.into_vec();
 
-            symbol = unsafe { zbar_symbol_next(symbol) };
-        }
+                data.truncate(packed_length);
+
+                data.into_boxed_slice()
+            },
+        };
 
-        Ok(result_array)
+        ZBarImage::from_owned(data, width, height, format)
     }
 }
 
-impl Default for ZBarImageScanner {
+impl Default for ZBarImage<'_> {
+    #[inline]
+    fn default() -> Self {
+        ZBarImage::new()
+    }
+}
+
+impl Drop for ZBarImage<'_> {
+    fn drop(&mut self) {
+        if !self.image.is_null() {
+            unsafe {
+                zbar_image_destroy(self.image);
+            }
+        }
+    }
+}
+
+// TODO: ----- Image Interface END-----
+
+// TODO: ----- Symbol Interface START-----
+
+/// A decoded symbol, borrowed from the image, the scanner or the symbol set it came from.
+#[derive(Debug, Copy, Clone)]
+pub struct ZBarSymbol<'a> {
+    symbol:   *const c_void,
+    _phantom: PhantomData<&'a c_void>,
+}
+
+impl<'a> ZBarSymbol<'a> {
+    #[inline]
+    pub fn symbol_type(&self) -> ZBarSymbolType {
+        ZBarSymbolType::from_raw(unsafe { zbar_symbol_get_type(self.symbol) })
+    }
+
+    /// The decoded data. It is usually text, but may be binary.
+    #[inline]
+    pub fn data(&self) -> &'a [u8] {
+        unsafe {
+            let data = zbar_symbol_get_data(self.symbol);
+
+            if data.is_null() {
+                return &[];
+            }
+
+            let data_length = zbar_symbol_get_data_length(self.symbol) as usize;
+
+            slice::from_raw_parts(data as *const u8, data_length)
+        }
+    }
+
+    /// A relative confidence metric. Larger values are better than smaller ones.
+    #[inline]
+    pub fn quality(&self) -> i32 {
+        unsafe { zbar_symbol_get_quality(self.symbol) }
+    }
+
+    /// The number of times this symbol has been seen when the result cache of the scanner is enabled. It is `-1` for uncached symbols.
+    #[inline]
+    pub fn count(&self) -> i32 {
+        unsafe { zbar_symbol_get_count(self.symbol) }
+    }
+
+    /// The general orientation of the symbol.
+    #[inline]
+    pub fn orientation(&self) -> ZBarOrientation {
+        let orientation = unsafe { zbar_symbol_get_orientation(self.symbol) };
+
+        ZBarOrientation::from_ordinal(orientation as isize)
+            .unwrap_or(ZBarOrientation::ZBarOrientUnknown)
+    }
+
+    /// The boolean configs which were enabled when the symbol was decoded.
+    #[inline]
+    pub fn configs(&self) -> ZBarConfigSet {
+        ZBarConfigSet::from_bits(unsafe { zbar_symbol_get_configs(self.symbol) })
+    }
+
+    /// The modifiers applied to the symbol.
+    #[inline]
+    pub fn modifiers(&self) -> ZBarModifierSet {
+        ZBarModifierSet::from_bits(unsafe { zbar_symbol_get_modifiers(self.symbol) })
+    }
+
+    /// Iterate over the child symbols of a composite symbol, such as the EAN-13 and the EAN-5 add-on of a `ZBarComposite` symbol. Other symbols have no children.
+    #[inline]
+    pub fn components(&self) -> ZBarSymbolIter<'a> {
+        ZBarSymbolIter::new(unsafe { zbar_symbol_first_component(self.symbol) })
+    }
+
+    /// The child symbols of a composite symbol as a symbol set.
+    #[inline]
+    pub fn component_set(&self) -> Option<ZBarSymbolSet> {
+        unsafe { ZBarSymbolSet::from_raw(zbar_symbol_get_components(self.symbol)) }
+    }
+
+    /// The points which outline the symbol.
+    pub fn points(&self) -> Vec<(i32, i32)> {
+        let loc_size = unsafe { zbar_symbol_get_loc_size(self.symbol) };
+
+        let mut points = Vec::with_capacity(loc_size as usize);
+
+        for i in 0..loc_size {
+            let x = unsafe { zbar_symbol_get_loc_x(self.symbol, i) };
+            let y = unsafe { zbar_symbol_get_loc_y(self.symbol, i) };
+
+            points.push((x, y));
+        }
+
+        points
+    }
+}
+
+/// An iterator over the symbols which are linked by `zbar_symbol_next`.
+#[derive(Debug, Clone)]
+pub struct ZBarSymbolIter<'a> {
+    symbol:   *const c_void,
+    _phantom: PhantomData<&'a c_void>,
+}
+
+impl<'a> ZBarSymbolIter<'a> {
+    #[inline]
+    fn new(symbol: *const c_void) -> ZBarSymbolIter<'a> {
+        ZBarSymbolIter {
+            symbol,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a> Iterator for ZBarSymbolIter<'a> {
+    type Item = ZBarSymbol<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.symbol.is_null() {
+            return None;
+        }
+
+        let symbol = ZBarSymbol {
+            symbol: self.symbol, _phantom: PhantomData
+        };
+
+        self.symbol = unsafe { zbar_symbol_next(self.symbol) };
+
+        Some(symbol)
+    }
+}
+
+// TODO: ----- Symbol Interface END-----
+
+// TODO: ----- Symbol Set Interface START-----
+
+/// A reference-counted set of decoded symbols. It keeps its symbols alive independently of the image or the scanner it was obtained from.
+#[derive(Debug)]
+pub struct ZBarSymbolSet {
+    symbols: *const c_void,
+}
+
+impl ZBarSymbolSet {
+    /// Take a new reference to `symbols`.
+    ///
+    /// # Safety
+    ///
+    /// `symbols` must be null or point to a valid symbol set.
+    unsafe fn from_raw(symbols: *const c_void) -> Option<ZBarSymbolSet> {
+        if symbols.is_null() {
+            return None;
+        }
+
+        zbar_symbol_set_ref(symbols, 1);
+
+        Some(ZBarSymbolSet {
+            symbols,
+        })
+    }
+
+    /// The number of filtered symbols in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        unsafe { zbar_symbol_set_get_size(self.symbols) }.max(0) as usize
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over the filtered symbols.
+    #[inline]
+    pub fn iter(&self) -> ZBarSymbolIter<'_> {
+        ZBarSymbolIter::new(unsafe { zbar_symbol_set_first_symbol(self.symbols) })
+    }
+
+    /// Iterate over all symbols in the set, including the partial and the filtered out ones.
+    #[inline]
+    pub fn iter_unfiltered(&self) -> ZBarSymbolIter<'_> {
+        ZBarSymbolIter::new(unsafe { zbar_symbol_set_first_unfiltered(self.symbols) })
+    }
+}
+
+impl Clone for ZBarSymbolSet {
+    #[inline]
+    fn clone(&self) -> Self {
+        unsafe {
+            zbar_symbol_set_ref(self.symbols, 1);
+        }
+
+        ZBarSymbolSet {
+            symbols: self.symbols
+        }
+    }
+}
+
+impl Drop for ZBarSymbolSet {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            zbar_symbol_set_ref(self.symbols, -1);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a ZBarSymbolSet {
+    type IntoIter = ZBarSymbolIter<'a>;
+    type Item = ZBarSymbol<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// TODO: ----- Symbol Set Interface END-----
+
+// TODO: ----- Image Scanner Interface START-----
+
+#[derive(Debug)]
+pub struct ZBarImageScanResult {
+    pub symbol_type: ZBarSymbolType,
+    pub data:        Vec<u8>,
+    pub points:      Vec<(i32, i32)>,
+    /// The child symbols of a composite symbol, each with its own type, data and location.
+    pub components:  Vec<ZBarImageScanResult>,
+}
+
+impl From<ZBarSymbol<'_>> for ZBarImageScanResult {
+    #[inline]
+    fn from(symbol: ZBarSymbol<'_>) -> Self {
+        ZBarImageScanResult {
+            symbol_type: symbol.symbol_type(),
+            data:        symbol.data().to_vec(),
+            points:      symbol.points(),
+            components:  symbol.components().map(ZBarImageScanResult::from).collect(),
+        }
+    }
+}
+
+/// The state of a callback registered with `ZBarImageScanner::on_decode`.
+struct DecodeHandler {
+    callback: Box<dyn FnMut(&ZBarSymbol)>,
+    /// A panic of the callback, which is resumed once ZBar returns.
+    panic:    Option<Box<dyn Any + Send>>,
+}
+
+/// The data handler which calls the callback of the `DecodeHandler` passed as `userdata` for every symbol of the image.
+unsafe extern "C" fn decode_handler(image: *mut c_void, userdata: *const c_void) {
+    let handler = &mut *(userdata as *mut DecodeHandler);
+
+    // unwinding into ZBar is undefined behavior
+    if handler.panic.is_none() {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            for symbol in ZBarSymbolIter::new(zbar_image_first_symbol(image)) {
+                (handler.callback)(&symbol);
+            }
+        }));
+
+        if let Err(payload) = result {
+            handler.panic = Some(payload);
+        }
+    }
+}
+
+/// Whether a config applies to the whole image scanner instead of a symbology.
+fn is_scanner_wide(config: ZBarConfig) -> bool {
+    match config {
+        ZBarConfig::ZBarCfgPosition | ZBarConfig::ZBarCfgXDensity | ZBarConfig::ZBarCfgYDensity => {
+            true
+        },
+        #[cfg(zbar_0_23)]
+        ZBarConfig::ZBarCfgTestInverted => true,
+        _ => false,
+    }
+}
+
+pub struct ZBarImageScanner {
+    scanner: *mut c_void,
+    config:  ScannerConfig,
+    handler: *mut DecodeHandler,
+}
+
+impl ZBarImageScanner {
+    /// Create an image scanner.
+    ///
+    /// # Panics
+    ///
+    /// Panics if ZBar cannot allocate the scanner. Use `try_new` to handle that case.
+    pub fn new() -> ZBarImageScanner {
+        ZBarImageScanner::try_new().unwrap()
+    }
+
+    /// Create an image scanner.
+    pub fn try_new() -> Result<ZBarImageScanner, ZBarRustError> {
+        #[cfg(feature = "dynamic-loading")]
+        load_library()?;
+
+        let scanner = unsafe { zbar_image_scanner_create() };
+
+        if scanner.is_null() {
+            return Err(ZBarRustError::NullHandle("zbar_image_scanner_create"));
+        }
+
+        Ok(ZBarImageScanner {
+            scanner,
+            config: ScannerConfig::new(),
+            handler: ptr::null_mut(),
+        })
+    }
+
+    /// Create a builder of scanners. See `ZBarImageScannerBuilder`.
+    #[inline]
+    pub fn builder() -> ZBarImageScannerBuilder {
+        ZBarImageScannerBuilder::new()
+    }
+
+    pub fn set_config(
+        &mut self,
+        symbology: ZBarSymbolType,
+        config: ZBarConfig,
+        value: isize,
+    ) -> Result<(), ZBarRustError> {
+        let result = unsafe {
+            zbar_image_scanner_set_config(
+                self.scanner,
+                symbology.to_raw() as c_int,
+                config.ordinal() as c_int,
+                value as c_int,
+            )
+        };
+        if result == 0 {
+            self.config.set(symbology, config, value);
+
+            Ok(())
+        } else {
+            Err(ZBarRustError::InvalidConfig {
+                symbology,
+                config,
+                value,
+            })
+        }
+    }
+
+    /// Read the current value of a config of a symbology.
+    ///
+    /// With ZBar 0.22 or later the value is asked from ZBar. With older versions, or when ZBar cannot report the config, the value set through this scanner is returned, and reading a config which has not been set returns a `ZBarRustError::UnsupportedConfig` error.
+    ///
+    /// The scanner-wide configs, `ZBarCfgXDensity`, `ZBarCfgYDensity`, `ZBarCfgPosition` and `ZBarCfgTestInverted`, are read with `ZBarNone` or `ZBarPartial`, which are the same for them.
+    pub fn get_config(
+        &self,
+        symbology: ZBarSymbolType,
+        config: ZBarConfig,
+    ) -> Result<isize, ZBarRustError> {
+        // ZBar only reads the scanner-wide configs with `ZBarPartial`
+        let symbology = match symbology {
+            ZBarSymbolType::ZBarNone if is_scanner_wide(config) => ZBarSymbolType::ZBarPartial,
+            symbology => symbology,
+        };
+
+        #[cfg(zbar_0_22)]
+        {
+            let mut value = 0;
+
+            let result = unsafe {
+                zbar_image_scanner_get_config(
+                    self.scanner,
+                    symbology.to_raw() as c_int,
+                    config.ordinal() as c_int,
+                    &mut value,
+                )
+            };
+
+            if result == 0 {
+                return Ok(value as isize);
+            }
+        }
+
+        // the settings for `ZBarNone` apply to `ZBarPartial` too
+        if let Some(value) = self.config.get(symbology, config) {
+            return Ok(value);
+        }
+
+        Err(ZBarRustError::UnsupportedConfig {
+            symbology,
+            config,
+        })
+    }
+
+    /// Take a snapshot of the settings which have been changed through this scanner. Applying it to a new scanner gives that scanner the same settings.
+    #[inline]
+    pub fn config(&self) -> ScannerConfig {
+        self.config.clone()
+    }
+
+    /// Apply a snapshot of settings taken from another scanner, or parsed from config strings.
+    #[inline]
+    pub fn apply_config(&mut self, config: &ScannerConfig) -> Result<(), ZBarRustError> {
+        config.apply(self)
+    }
+
+    /// Apply a ZBar config string such as `ean13.disable` or `qrcode.min-length=4`. See `parse_config` for the grammar.
+    pub fn parse_config(&mut self, config: &str) -> Result<(), ZBarRustError> {
+        let (symbology, config, value) = parse_config(config)?;
+
+        self.set_config(symbology, config, value)
+    }
+
+    pub fn destroy(mut self) {
+        unsafe {
+            zbar_image_scanner_destroy(self.scanner);
+            self.scanner = ptr::null_mut();
+        }
+    }
+
+    pub fn scan_y800<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        self.scan(data, width, height, FourCC::Y800)
+    }
+
+    pub fn scan_gray<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        self.scan(data, width, height, FourCC::GREY)
+    }
+
+    /// Scan an RGB image with 3 bytes per pixel, ordered as R G B. It is converted to grayscale before scanning.
+    pub fn scan_rgb8<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        let data = data.as_ref();
+
+        fourcc::check_data_length(FourCC::RGB3, width, height, data.len())?;
+
+        self.scan_y800(convert::packed_rgb_to_luma(data, 3, [0, 1, 2]), width, height)
+    }
+
+    /// Scan an RGBA image with 4 bytes per pixel, ordered as R G B A. It is converted to grayscale before scanning and the alpha channel is ignored.
+    pub fn scan_rgba8<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        let data = data.as_ref();
+
+        // any 32-bit packed format has the same size
+        fourcc::check_data_length(FourCC::RGB4, width, height, data.len())?;
+
+        self.scan_y800(convert::packed_rgb_to_luma(data, 4, [0, 1, 2]), width, height)
+    }
+
+    /// Scan a BGR image with 3 bytes per pixel, ordered as B G R. It is converted to grayscale before scanning.
+    pub fn scan_bgr8<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        let data = data.as_ref();
+
+        fourcc::check_data_length(FourCC::BGR3, width, height, data.len())?;
+
+        self.scan_y800(convert::packed_rgb_to_luma(data, 3, [2, 1, 0]), width, height)
+    }
+
+    /// Scan a packed YUV 4:2:2 image, ordered as Y0 U Y1 V. Only its luminance samples are scanned.
+    pub fn scan_yuyv<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        let data = data.as_ref();
+
+        fourcc::check_data_length(FourCC::YUYV, width, height, data.len())?;
+
+        self.scan_y800(convert::packed_yuv_to_luma(data, width as usize, true), width, height)
+    }
+
+    /// Scan an NV12 image, a Y plane followed by an interleaved UV plane. Only the Y plane is scanned, without being copied.
+    pub fn scan_nv12<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        self.scan_luma_plane(data.as_ref(), width, height, FourCC::NV12)
+    }
+
+    /// Scan an I420 image, a Y plane followed by a U plane and a V plane. Only the Y plane is scanned, without being copied.
+    pub fn scan_i420<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        self.scan_luma_plane(data.as_ref(), width, height, FourCC::I420)
+    }
+
+    /// Scan an RGB image like `scan_rgb8`, whose rows start every `stride` bytes.
+    pub fn scan_rgb8_strided<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+        stride: usize,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        let data =
+            convert::pack_rows(data.as_ref(), FourCC::RGB3, width, height, &[(0, stride)], false)?;
+
+        self.scan_rgb8(data, width, height)
+    }
+
+    /// Scan an RGBA image like `scan_rgba8`, whose rows start every `stride` bytes.
+    pub fn scan_rgba8_strided<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+        stride: usize,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        let data =
+            convert::pack_rows(data.as_ref(), FourCC::RGB4, width, height, &[(0, stride)], false)?;
+
+        self.scan_rgba8(data, width, height)
+    }
+
+    /// Scan a BGR image like `scan_bgr8`, whose rows start every `stride` bytes.
+    pub fn scan_bgr8_strided<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+        stride: usize,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        let data =
+            convert::pack_rows(data.as_ref(), FourCC::BGR3, width, height, &[(0, stride)], false)?;
+
+        self.scan_bgr8(data, width, height)
+    }
+
+    /// Scan a YUYV image like `scan_yuyv`, whose rows start every `stride` bytes.
+    pub fn scan_yuyv_strided<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+        stride: usize,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        let data =
+            convert::pack_rows(data.as_ref(), FourCC::YUYV, width, height, &[(0, stride)], false)?;
+
+        self.scan_yuyv(data, width, height)
+    }
+
+    /// Scan an NV12 image like `scan_nv12`. `planes` holds the `(offset, stride)` of the Y plane and of the UV plane, as `ZBarImage::from_borrowed_strided` does.
+    pub fn scan_nv12_strided<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+        planes: [(usize, usize); 2],
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        let luma = convert::pack_rows(data.as_ref(), FourCC::NV12, width, height, &planes, true)?;
+
+        self.scan_y800(luma, width, height)
+    }
+
+    /// Scan an I420 image like `scan_i420`. `planes` holds the `(offset, stride)` of the Y, U and V planes, as `ZBarImage::from_borrowed_strided` does.
+    pub fn scan_i420_strided<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+        planes: [(usize, usize); 3],
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        let luma = convert::pack_rows(data.as_ref(), FourCC::I420, width, height, &planes, true)?;
+
+        self.scan_y800(luma, width, height)
+    }
+
+    /// Scan the leading Y plane of a planar or semi-planar YUV image.
+    fn scan_luma_plane(
+        &mut self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        format: FourCC,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        fourcc::check_data_length(format, width, height, data.len())?;
+
+        let luma_length = width as usize * height as usize;
+
+        self.scan_y800(&data[..luma_length], width, height)
+    }
+
+    /// Scan an image whose pixel format is `format`.
+    ///
+    /// The length of `data` must match the size that `width`, `height` and `format` imply, otherwise a `ZBarRustError::BufferSizeMismatch` error is returned before the data is passed to ZBar.
+    pub fn scan<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+        format: FourCC,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        self.scan_inner(data.as_ref(), width, height, format, None)
+    }
+
+    /// Scan an image whose pixel format is `format` and whose rows are padded. See `ZBarImage::from_borrowed_strided` for the meaning of `planes`.
+    ///
+    /// The data is only copied if its rows are padded or its planes do not follow each other.
+    pub fn scan_strided<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+        format: FourCC,
+        planes: &[(usize, usize)],
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        let data = convert::pack_rows(data.as_ref(), format, width, height, planes, false)?;
+
+        self.scan_inner(&data, width, height, format, None)
+    }
+
+    /// Scan an image of the `image` crate. See `ZBarImageSource` for how it is converted.
+    #[cfg(feature = "image")]
+    #[inline]
+    pub fn scan_source<I: ZBarImageSource + ?Sized>(
+        &mut self,
+        image: &I,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        image.scan_with(self)
+    }
+
+    /// Scan only a region of an image whose pixel format is `format`. The points of the results are in the coordinates of the whole image.
+    ///
+    /// The region must lie within the image, otherwise a `ZBarRustError::RegionOutOfBounds` error is returned.
+    pub fn scan_region<D: AsRef<[u8]>>(
+        &mut self,
+        data: D,
+        width: u32,
+        height: u32,
+        format: FourCC,
+        region: ZBarRegion,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        if !region.is_within(width, height) {
+            return Err(ZBarRustError::RegionOutOfBounds {
+                region,
+                width,
+                height,
+            });
+        }
+
+        self.scan_inner(data.as_ref(), width, height, format, Some(region))
+    }
+
+    fn scan_inner(
+        &mut self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        format: FourCC,
+        region: Option<ZBarRegion>,
+    ) -> Result<Vec<ZBarImageScanResult>, ZBarRustError> {
+        let mut image = ZBarImage::from_borrowed(data, width, height, format)?;
+
+        if let Some(region) = region {
+            image.set_crop(region);
+        }
+
+        self.scan_image(&mut image)?;
+
+        Ok(image.symbols().map(ZBarImageScanResult::from).collect())
+    }
+
+    /// Scan an image. Its results are available through its `symbols` and `symbol_set` methods. Returns the number of symbols found.
+    ///
+    /// ZBar only scans `Y800` and `GREY` images, other formats cause a `ZBarRustError::UnsupportedFormat` error. An image without data causes a `ZBarRustError::NoImageData` error.
+    pub fn scan_image(&mut self, image: &mut ZBarImage) -> Result<usize, ZBarRustError> {
+        // ZBar reads the data without checking it
+        image.check_has_data()?;
+        image.check_data_length(image.format(), image.width(), image.height())?;
+
+        let n = unsafe { zbar_scan_image(self.scanner, image.image) };
+
+        if !self.handler.is_null() {
+            if let Some(payload) = unsafe { (*self.handler).panic.take() } {
+                panic::resume_unwind(payload);
+            }
+        }
+
+        if n < 0 {
+            return Err(ZBarRustError::UnsupportedFormat(image.format()));
+        }
+
+        Ok(n as usize)
+    }
+
+    /// Call `callback` with every symbol decoded from an image. ZBar calls its handler once per image, after the whole image has been scanned and only if any symbol has been found, so `callback` is called for each symbol of the image, in order, just before `scan_image` returns. It replaces the callback registered before.
+    ///
+    /// A panic of `callback` is caught before it reaches ZBar, and resumed once the scan returns. The remaining symbols of that image are skipped.
+    pub fn on_decode<F: FnMut(&ZBarSymbol) + 'static>(&mut self, callback: F) {
+        let handler = Box::into_raw(Box::new(DecodeHandler {
+            callback: Box::new(callback),
+            panic:    None,
+        }));
+
+        unsafe {
+            zbar_image_scanner_set_data_handler(
+                self.scanner,
+                Some(decode_handler),
+                handler as *const c_void,
+            );
+        }
+
+        self.free_handler();
+
+        self.handler = handler;
+    }
+
+    /// Remove the callback registered with `on_decode`.
+    pub fn remove_on_decode(&mut self) {
+        unsafe {
+            zbar_image_scanner_set_data_handler(self.scanner, None, ptr::null());
+        }
+
+        self.free_handler();
+    }
+
+    fn free_handler(&mut self) {
+        if !self.handler.is_null() {
+            unsafe {
+                drop(Box::from_raw(self.handler));
+            }
+
+            self.handler = ptr::null_mut();
+        }
+    }
+
+    /// The symbol set decoded by the last scan. Unlike `symbols`, the set stays valid after the next scan.
+    #[inline]
+    pub fn results(&self) -> Option<ZBarSymbolSet> {
+        unsafe { ZBarSymbolSet::from_raw(zbar_image_scanner_get_results(self.scanner)) }
+    }
+
+    /// Iterate over the symbols decoded by the last scan. They are available until the next scan.
+    #[inline]
+    pub fn symbols(&self) -> ZBarSymbolIter<'_> {
+        let symbols = unsafe { zbar_image_scanner_get_results(self.scanner) };
+
+        if symbols.is_null() {
+            return ZBarSymbolIter::new(ptr::null());
+        }
+
+        ZBarSymbolIter::new(unsafe { zbar_symbol_set_first_symbol(symbols) })
+    }
+}
+
+impl Default for ZBarImageScanner {
     #[inline]
     fn default() -> Self {
         ZBarImageScanner::new()
@@ -464,6 +1629,9 @@ impl Drop for ZBarImageScanner {
                 zbar_image_scanner_destroy(self.scanner);
             }
         }
+
+        // ZBar does not call the handler once the scanner is destroyed
+        self.free_handler();
     }
 }
 // TODO: ----- Image Scanner Interface END-----
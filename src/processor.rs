@@ -0,0 +1,164 @@
+use std::{ptr, time::Duration};
+
+use enum_ordinalize::Ordinalize;
+use libc::c_int;
+
+#[cfg(feature = "dynamic-loading")]
+use crate::load_library;
+use crate::{
+    sys::*, ZBarConfig, ZBarError, ZBarImage, ZBarRustError, ZBarSymbolSet, ZBarSymbolType,
+};
+
+/// A high-level processor, which runs ZBar's own image scanner and can process images in a thread of its own.
+///
+/// It is used headless, without a video device or a window, so it processes the images passed to `process_image`. It is not available with the `vendored` feature, which does not build ZBar's processor.
+///
+/// ```rust,ignore
+/// let mut processor = ZBarProcessor::new(true);
+///
+/// processor.process_image(&mut image).unwrap();
+///
+/// for symbol in image.symbols() {
+///     println!("{:?}", symbol.data());
+/// }
+/// ```
+pub struct ZBarProcessor {
+    processor: *mut zbar_processor_t,
+}
+
+impl ZBarProcessor {
+    /// Create a processor, which uses a thread of its own if `threaded` is set and ZBar supports threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if ZBar cannot create or initialize the processor. Use `try_new` to handle that case.
+    pub fn new(threaded: bool) -> ZBarProcessor {
+        ZBarProcessor::try_new(threaded).unwrap()
+    }
+
+    /// Create a processor, which uses a thread of its own if `threaded` is set and ZBar supports threads.
+    pub fn try_new(threaded: bool) -> Result<ZBarProcessor, ZBarRustError> {
+        #[cfg(feature = "dynamic-loading")]
+        load_library()?;
+
+        let processor = unsafe { zbar_processor_create(threaded as c_int) };
+
+        if processor.is_null() {
+            return Err(ZBarRustError::NullHandle("zbar_processor_create"));
+        }
+
+        let processor = ZBarProcessor {
+            processor,
+        };
+
+        // no video device and no window
+        if unsafe { zbar_processor_init(processor.processor, ptr::null(), 0) } != 0 {
+            return Err(processor.error());
+        }
+
+        Ok(processor)
+    }
+
+    /// The last error reported by ZBar for this processor.
+    fn error(&self) -> ZBarRustError {
+        let code = unsafe { _zbar_get_error_code(self.processor as *const _) };
+
+        ZBarRustError::Library(
+            ZBarError::from_ordinal(code as isize).unwrap_or(ZBarError::ZBarErrNum),
+        )
+    }
+
+    pub fn set_config(
+        &mut self,
+        symbology: ZBarSymbolType,
+        config: ZBarConfig,
+        value: isize,
+    ) -> Result<(), ZBarRustError> {
+        let result = unsafe {
+            zbar_processor_set_config(
+                self.processor,
+                symbology.to_raw() as c_int,
+                config.ordinal() as c_int,
+                value as c_int,
+            )
+        };
+
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(ZBarRustError::InvalidConfig {
+                symbology,
+                config,
+                value,
+            })
+        }
+    }
+
+    /// Scan an image. Unlike `ZBarImageScanner::scan_image`, images of any format ZBar can convert are accepted. Its results are available through its `symbols` and `symbol_set` methods, and through `results`.
+    ///
+    /// An image without data causes a `ZBarRustError::NoImageData` error.
+    pub fn process_image(&mut self, image: &mut ZBarImage) -> Result<(), ZBarRustError> {
+        // ZBar converts the data without checking it
+        image.check_has_data()?;
+        image.check_data_length(image.format(), image.width(), image.height())?;
+
+        if unsafe { zbar_process_image(self.processor, image.image) } < 0 {
+            return Err(self.error());
+        }
+
+        Ok(())
+    }
+
+    /// The symbol set decoded by the last processed image.
+    pub fn results(&self) -> Option<ZBarSymbolSet> {
+        unsafe {
+            let symbols = zbar_processor_get_results(self.processor);
+
+            let results = ZBarSymbolSet::from_raw(symbols);
+
+            // ZBar has already taken a reference for the caller
+            if !symbols.is_null() {
+                zbar_symbol_set_ref(symbols, -1);
+            }
+
+            results
+        }
+    }
+
+    /// Wait for a key press for at most `timeout`, or forever with `None`. Returns the key, or `None` when the time is up.
+    ///
+    /// The processor has no window, so no key can be pressed. ZBar then sleeps for `timeout` and reports that the window is closed, which is returned as `None` too. With a `None` timeout, ZBar does not wait at all and `None` is returned at once.
+    pub fn user_wait(&mut self, timeout: Option<Duration>) -> Result<Option<i32>, ZBarRustError> {
+        let timeout = match timeout {
+            Some(timeout) => timeout.as_millis().min(c_int::MAX as u128) as c_int,
+            None => -1,
+        };
+
+        match unsafe { zbar_processor_user_wait(self.processor, timeout) } {
+            0 => Ok(None),
+            key if key > 0 => Ok(Some(key)),
+            _ => match self.error() {
+                // there is no window to take input from
+                ZBarRustError::Library(ZBarError::ZBarErrClosed) => Ok(None),
+                err => Err(err),
+            },
+        }
+    }
+
+    pub fn destroy(mut self) {
+        unsafe {
+            zbar_processor_destroy(self.processor);
+            self.processor = ptr::null_mut();
+        }
+    }
+}
+
+impl Drop for ZBarProcessor {
+    fn drop(&mut self) {
+        if !self.processor.is_null() {
+            unsafe {
+                zbar_processor_destroy(self.processor);
+            }
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use std::ptr;
+
+use enum_ordinalize::Ordinalize;
+use libc::{c_int, c_uint};
+
+#[cfg(feature = "dynamic-loading")]
+use crate::load_library;
+use crate::{sys::*, ZBarColor, ZBarDecoder, ZBarRustError, ZBarSymbolType};
+
+/// The number of fractional bits of the positions and widths reported by ZBar, which are in 1/32 of a sample.
+const FIXED_BITS: i32 = 5;
+
+/// An edge found by `ZBarScanner::scan_line`, between the element before it and an element of `color`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ZBarEdge {
+    /// The position of the edge from the start of the line, in 1/32 of a sample.
+    pub position: u32,
+    /// The width of the element which ends at this edge, in 1/32 of a sample.
+    pub width:    u32,
+    /// The color of the element which starts at this edge.
+    pub color:    ZBarColor,
+}
+
+/// A symbol decoded by `ZBarScanner::scan_line`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ZBarLineScanResult {
+    pub symbol_type: ZBarSymbolType,
+    pub data:        Vec<u8>,
+    /// The position of the edge where the symbol was decoded, in 1/32 of a sample.
+    pub position:    u32,
+}
+
+/// The edges and symbols found in a line.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ZBarLineScan {
+    pub edges:   Vec<ZBarEdge>,
+    pub results: Vec<ZBarLineScanResult>,
+}
+
+/// A linear intensity sample stream scanner, which finds the edges of bars and spaces in a single row of samples, such as a row of a line-scan camera, and passes their widths to a `ZBarDecoder`.
+///
+/// ```rust,ignore
+/// let mut scanner = ZBarScanner::with_decoder(ZBarDecoder::new());
+///
+/// for result in scanner.scan_line(&row).results {
+///     println!("{:?}: {:?}", result.symbol_type, result.data);
+/// }
+/// ```
+///
+/// The decoder is owned by the scanner, which keeps using it until it is destroyed, so it can only be borrowed immutably. Configure it before passing it, or take it back with `into_decoder`.
+pub struct ZBarScanner {
+    scanner: *mut zbar_scanner_t,
+    decoder: Option<ZBarDecoder>,
+}
+
+impl ZBarScanner {
+    /// Create a scanner which only finds edges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if ZBar cannot allocate the scanner. Use `try_new` to handle that case.
+    pub fn new() -> ZBarScanner {
+        ZBarScanner::try_new().unwrap()
+    }
+
+    /// Create a scanner which only finds edges.
+    #[inline]
+    pub fn try_new() -> Result<ZBarScanner, ZBarRustError> {
+        ZBarScanner::create(None)
+    }
+
+    /// Create a scanner which passes the widths it finds to `decoder`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if ZBar cannot allocate the scanner. Use `try_with_decoder` to handle that case.
+    pub fn with_decoder(decoder: ZBarDecoder) -> ZBarScanner {
+        ZBarScanner::try_with_decoder(decoder).unwrap()
+    }
+
+    /// Create a scanner which passes the widths it finds to `decoder`.
+    #[inline]
+    pub fn try_with_decoder(decoder: ZBarDecoder) -> Result<ZBarScanner, ZBarRustError> {
+        ZBarScanner::create(Some(decoder))
+    }
+
+    fn create(decoder: Option<ZBarDecoder>) -> Result<ZBarScanner, ZBarRustError> {
+        #[cfg(feature = "dynamic-loading")]
+        load_library()?;
+
+        let scanner = unsafe {
+            zbar_scanner_create(decoder.as_ref().map_or(ptr::null_mut(), ZBarDecoder::as_ptr))
+        };
+
+        if scanner.is_null() {
+            return Err(ZBarRustError::NullHandle("zbar_scanner_create"));
+        }
+
+        Ok(ZBarScanner {
+            scanner,
+            decoder,
+        })
+    }
+
+    /// The decoder which the widths are passed to.
+    #[inline]
+    pub fn decoder(&self) -> Option<&ZBarDecoder> {
+        self.decoder.as_ref()
+    }
+
+    /// Destroy the scanner and take back its decoder, for example to change its configs.
+    #[inline]
+    pub fn into_decoder(mut self) -> Option<ZBarDecoder> {
+        self.decoder.take()
+    }
+
+    /// Clear the state of the scanner, and reset the decoder.
+    #[inline]
+    pub fn reset(&mut self) -> ZBarSymbolType {
+        ZBarSymbolType::from_raw(unsafe { zbar_scanner_reset(self.scanner) })
+    }
+
+    /// Start a new line. The last edge of the previous line is flushed first, and the symbol it completes is returned.
+    #[inline]
+    pub fn new_scan(&mut self) -> ZBarSymbolType {
+        ZBarSymbolType::from_raw(unsafe { zbar_scanner_new_scan(self.scanner) })
+    }
+
+    /// Report the edge which is still pending at the end of a line, as if a quiet zone followed. Returns the symbol it completes.
+    #[inline]
+    pub fn flush(&mut self) -> ZBarSymbolType {
+        ZBarSymbolType::from_raw(unsafe { zbar_scanner_flush(self.scanner) })
+    }
+
+    /// Feed the next intensity sample. Returns the type of the symbol which has just been decoded, `ZBarPartial` if an edge was found but no symbol was completed, or `ZBarNone`.
+    ///
+    /// With a decoder, an edge which does not complete any part of a symbol returns `ZBarNone`, so use `edge` to tell whether an edge was found.
+    #[inline]
+    pub fn scan_y(&mut self, y: i32) -> ZBarSymbolType {
+        ZBarSymbolType::from_raw(unsafe { zbar_scan_y(self.scanner, y as c_int) })
+    }
+
+    /// The width of the element which ends at the last edge, in 1/32 of a sample.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        unsafe { zbar_scanner_get_width(self.scanner) as u32 }
+    }
+
+    /// The position of the last edge from the start of the line, minus `offset`, both in 1/32 of a sample. The result has `precision` fractional bits, so `0` gives whole samples.
+    #[inline]
+    pub fn edge(&self, offset: u32, precision: i32) -> u32 {
+        unsafe { zbar_scanner_get_edge(self.scanner, offset as c_uint, precision as c_int) as u32 }
+    }
+
+    /// The color of the element which starts at the last edge.
+    #[inline]
+    pub fn color(&self) -> ZBarColor {
+        let color = unsafe { zbar_scanner_get_color(self.scanner) };
+
+        ZBarColor::from_ordinal(color as isize).unwrap_or(ZBarColor::ZBarSpace)
+    }
+
+    /// Scan a line of intensity samples, such as `&[u8]` pixels or `&[i32]` sensor values, as a line of its own, and collect the edges and symbols found in it.
+    pub fn scan_line<T: Copy + Into<i32>>(&mut self, samples: &[T]) -> ZBarLineScan {
+        let mut scan = ZBarLineScan::default();
+
+        self.new_scan();
+
+        let mut last_edge = self.edge(0, FIXED_BITS);
+
+        for sample in samples.iter().copied() {
+            let symbol_type = self.scan_y(sample.into());
+
+            self.record(symbol_type, &mut last_edge, &mut scan);
+        }
+
+        // end the line with a quiet zone, like the image scanner does
+        for _ in 0..2 {
+            let symbol_type = self.flush();
+
+            self.record(symbol_type, &mut last_edge, &mut scan);
+        }
+
+        // this also clears the edges, so only a decoded symbol is taken
+        let symbol_type = self.new_scan();
+
+        self.record_result(symbol_type, last_edge, &mut scan);
+
+        scan
+    }
+
+    fn record(&self, symbol_type: ZBarSymbolType, last_edge: &mut u32, scan: &mut ZBarLineScan) {
+        let position = self.edge(0, FIXED_BITS);
+
+        if position != *last_edge {
+            *last_edge = position;
+
+            scan.edges.push(ZBarEdge {
+                position,
+                width: self.width(),
+                color: self.color(),
+            });
+        }
+
+        self.record_result(symbol_type, position, scan);
+    }
+
+    fn record_result(&self, symbol_type: ZBarSymbolType, position: u32, scan: &mut ZBarLineScan) {
+        if matches!(symbol_type, ZBarSymbolType::ZBarNone | ZBarSymbolType::ZBarPartial) {
+            return;
+        }
+
+        if let Some(decoder) = self.decoder.as_ref() {
+            scan.results.push(ZBarLineScanResult {
+                symbol_type,
+                data: decoder.data().to_vec(),
+                position,
+            });
+        }
+    }
+
+    pub fn destroy(mut self) {
+        unsafe {
+            zbar_scanner_destroy(self.scanner);
+            self.scanner = ptr::null_mut();
+        }
+    }
+}
+
+impl Default for ZBarScanner {
+    #[inline]
+    fn default() -> Self {
+        ZBarScanner::new()
+    }
+}
+
+impl Drop for ZBarScanner {
+    fn drop(&mut self) {
+        // the scanner refers to the decoder, which is dropped after this
+        if !self.scanner.is_null() {
+            unsafe {
+                zbar_scanner_destroy(self.scanner);
+            }
+        }
+    }
+}
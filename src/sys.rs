@@ -0,0 +1,512 @@
+/*!
+Raw bindings of `zbar.h`.
+
+The handle types are opaque and aliased to `c_void`. Enums of the header are passed as `c_int`, and their values are available as constants. Functions which ZBar added after 0.10 are only declared when the linked version has them, and the `static inline` helpers of the header are not bound.
+*/
+
+#![allow(non_camel_case_types)]
+
+use libc::{c_char, c_float, c_int, c_uint, c_ulong, c_void};
+
+/// Declare ZBar functions, which are linked at build time, or looked up in the library loaded at runtime with the `dynamic-loading` feature.
+macro_rules! zbar_extern {
+    ($($(#[$attr:meta])* pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+        #[cfg(not(feature = "dynamic-loading"))]
+        #[link(name = "zbar")]
+        extern "C" {
+            $($(#[$attr])* pub fn $name($($arg: $ty),*) $(-> $ret)?;)*
+        }
+
+        $(
+            #[cfg(feature = "dynamic-loading")]
+            $(#[$attr])*
+            #[allow(clippy::missing_safety_doc)]
+            pub unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                type Function = unsafe extern "C" fn($($ty),*) $(-> $ret)?;
+
+                static FUNCTION: std::sync::OnceLock<Function> = std::sync::OnceLock::new();
+
+                let function = *FUNCTION.get_or_init(|| {
+                    crate::dynamic::function::<Function>(concat!(stringify!($name), "\0"))
+                });
+
+                function($($arg),*)
+            }
+        )*
+    };
+}
+
+pub type zbar_image_t = c_void;
+pub type zbar_symbol_t = c_void;
+pub type zbar_symbol_set_t = c_void;
+pub type zbar_image_scanner_t = c_void;
+pub type zbar_decoder_t = c_void;
+pub type zbar_scanner_t = c_void;
+pub type zbar_processor_t = c_void;
+pub type zbar_video_t = c_void;
+pub type zbar_window_t = c_void;
+
+pub type zbar_color_t = c_int;
+pub type zbar_symbol_type_t = c_int;
+pub type zbar_orientation_t = c_int;
+pub type zbar_error_t = c_int;
+pub type zbar_config_t = c_int;
+pub type zbar_modifier_t = c_int;
+pub type video_control_type_t = c_int;
+
+/// Called when the data of an image is no longer used by ZBar.
+pub type zbar_image_cleanup_handler_t = unsafe extern "C" fn(image: *mut zbar_image_t);
+/// Called with an image after symbols are found in it.
+pub type zbar_image_data_handler_t =
+    unsafe extern "C" fn(image: *mut zbar_image_t, userdata: *const c_void);
+/// Called when a decoder finds a symbol.
+pub type zbar_decoder_handler_t = unsafe extern "C" fn(decoder: *mut zbar_decoder_t);
+
+pub const ZBAR_SPACE: zbar_color_t = 0;
+pub const ZBAR_BAR: zbar_color_t = 1;
+
+pub const ZBAR_NONE: zbar_symbol_type_t = 0;
+pub const ZBAR_PARTIAL: zbar_symbol_type_t = 1;
+pub const ZBAR_EAN2: zbar_symbol_type_t = 2;
+pub const ZBAR_EAN5: zbar_symbol_type_t = 5;
+pub const ZBAR_EAN8: zbar_symbol_type_t = 8;
+pub const ZBAR_UPCE: zbar_symbol_type_t = 9;
+pub const ZBAR_ISBN10: zbar_symbol_type_t = 10;
+pub const ZBAR_UPCA: zbar_symbol_type_t = 12;
+pub const ZBAR_EAN13: zbar_symbol_type_t = 13;
+pub const ZBAR_ISBN13: zbar_symbol_type_t = 14;
+pub const ZBAR_COMPOSITE: zbar_symbol_type_t = 15;
+pub const ZBAR_I25: zbar_symbol_type_t = 25;
+pub const ZBAR_DATABAR: zbar_symbol_type_t = 34;
+pub const ZBAR_DATABAR_EXP: zbar_symbol_type_t = 35;
+pub const ZBAR_CODABAR: zbar_symbol_type_t = 38;
+pub const ZBAR_CODE39: zbar_symbol_type_t = 39;
+pub const ZBAR_PDF417: zbar_symbol_type_t = 57;
+pub const ZBAR_QRCODE: zbar_symbol_type_t = 64;
+#[cfg(zbar_0_23)]
+pub const ZBAR_SQCODE: zbar_symbol_type_t = 80;
+pub const ZBAR_CODE93: zbar_symbol_type_t = 93;
+pub const ZBAR_CODE128: zbar_symbol_type_t = 128;
+#[cfg(zbar_0_23)]
+pub const ZBAR_CODE32: zbar_symbol_type_t = 129;
+pub const ZBAR_SYMBOL: zbar_symbol_type_t = 0x00FF;
+pub const ZBAR_ADDON2: zbar_symbol_type_t = 0x0200;
+pub const ZBAR_ADDON5: zbar_symbol_type_t = 0x0500;
+pub const ZBAR_ADDON: zbar_symbol_type_t = 0x0700;
+
+pub const ZBAR_ORIENT_UNKNOWN: zbar_orientation_t = -1;
+pub const ZBAR_ORIENT_UP: zbar_orientation_t = 0;
+pub const ZBAR_ORIENT_RIGHT: zbar_orientation_t = 1;
+pub const ZBAR_ORIENT_DOWN: zbar_orientation_t = 2;
+pub const ZBAR_ORIENT_LEFT: zbar_orientation_t = 3;
+
+pub const ZBAR_OK: zbar_error_t = 0;
+pub const ZBAR_ERR_NOMEM: zbar_error_t = 1;
+pub const ZBAR_ERR_INTERNAL: zbar_error_t = 2;
+pub const ZBAR_ERR_UNSUPPORTED: zbar_error_t = 3;
+pub const ZBAR_ERR_INVALID: zbar_error_t = 4;
+pub const ZBAR_ERR_SYSTEM: zbar_error_t = 5;
+pub const ZBAR_ERR_LOCKING: zbar_error_t = 6;
+pub const ZBAR_ERR_BUSY: zbar_error_t = 7;
+pub const ZBAR_ERR_XDISPLAY: zbar_error_t = 8;
+pub const ZBAR_ERR_XPROTO: zbar_error_t = 9;
+pub const ZBAR_ERR_CLOSED: zbar_error_t = 10;
+pub const ZBAR_ERR_WINAPI: zbar_error_t = 11;
+pub const ZBAR_ERR_NUM: zbar_error_t = 12;
+
+pub const ZBAR_CFG_ENABLE: zbar_config_t = 0;
+pub const ZBAR_CFG_ADD_CHECK: zbar_config_t = 1;
+pub const ZBAR_CFG_EMIT_CHECK: zbar_config_t = 2;
+pub const ZBAR_CFG_ASCII: zbar_config_t = 3;
+#[cfg(zbar_0_23)]
+pub const ZBAR_CFG_BINARY: zbar_config_t = 4;
+#[cfg(zbar_0_23)]
+pub const ZBAR_CFG_NUM: zbar_config_t = 5;
+#[cfg(not(zbar_0_23))]
+pub const ZBAR_CFG_NUM: zbar_config_t = 4;
+pub const ZBAR_CFG_MIN_LEN: zbar_config_t = 0x20;
+pub const ZBAR_CFG_MAX_LEN: zbar_config_t = 0x21;
+pub const ZBAR_CFG_UNCERTAINTY: zbar_config_t = 0x40;
+pub const ZBAR_CFG_POSITION: zbar_config_t = 0x80;
+#[cfg(zbar_0_23)]
+pub const ZBAR_CFG_TEST_INVERTED: zbar_config_t = 0x81;
+pub const ZBAR_CFG_X_DENSITY: zbar_config_t = 0x100;
+pub const ZBAR_CFG_Y_DENSITY: zbar_config_t = 0x101;
+
+pub const ZBAR_MOD_GS1: zbar_modifier_t = 0;
+pub const ZBAR_MOD_AIM: zbar_modifier_t = 1;
+pub const ZBAR_MOD_NUM: zbar_modifier_t = 2;
+
+pub const VIDEO_CNTL_INTEGER: video_control_type_t = 1;
+pub const VIDEO_CNTL_MENU: video_control_type_t = 2;
+pub const VIDEO_CNTL_BUTTON: video_control_type_t = 3;
+pub const VIDEO_CNTL_INTEGER64: video_control_type_t = 4;
+pub const VIDEO_CNTL_STRING: video_control_type_t = 5;
+pub const VIDEO_CNTL_BOOLEAN: video_control_type_t = 6;
+
+/// An entry of the menu of a video control.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct video_control_menu_t {
+    pub name:  *mut c_char,
+    pub value: i64,
+}
+
+/// A control of a video device, such as its brightness.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct video_controls_t {
+    pub name:      *mut c_char,
+    pub group:     *mut c_char,
+    pub type_:     video_control_type_t,
+    pub min:       i64,
+    pub max:       i64,
+    pub def:       i64,
+    pub step:      u64,
+    pub menu_size: c_uint,
+    pub menu:      *mut video_control_menu_t,
+}
+
+/// A resolution supported by a video device.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct video_resolution_t {
+    pub width:   c_uint,
+    pub height:  c_uint,
+    pub max_fps: c_float,
+}
+
+// TODO: ----- General Interface START-----
+
+zbar_extern! {
+    /// ZBar older than 0.22 only takes the first two arguments and ignores the third one.
+    pub fn zbar_version(major: *mut c_uint, minor: *mut c_uint, patch: *mut c_uint) -> c_int;
+    pub fn zbar_set_verbosity(verbosity: c_int);
+    pub fn zbar_increase_verbosity();
+    pub fn zbar_get_symbol_name(sym: zbar_symbol_type_t) -> *const c_char;
+    pub fn zbar_get_addon_name(sym: zbar_symbol_type_t) -> *const c_char;
+    pub fn zbar_get_config_name(config: zbar_config_t) -> *const c_char;
+    pub fn zbar_get_modifier_name(modifier: zbar_modifier_t) -> *const c_char;
+    pub fn zbar_get_orientation_name(orientation: zbar_orientation_t) -> *const c_char;
+    pub fn zbar_parse_config(
+        config_string: *const c_char,
+        symbology: *mut zbar_symbol_type_t,
+        config: *mut zbar_config_t,
+        value: *mut c_int,
+    ) -> c_int;
+    pub fn _zbar_error_spew(object: *const c_void, verbosity: c_int) -> c_int;
+    pub fn _zbar_error_string(object: *const c_void, verbosity: c_int) -> *const c_char;
+    pub fn _zbar_get_error_code(object: *const c_void) -> zbar_error_t;
+}
+
+// TODO: ----- General Interface END-----
+
+// TODO: ----- Symbol Interface START-----
+
+zbar_extern! {
+    pub fn zbar_symbol_ref(symbol: *const zbar_symbol_t, refs: c_int);
+    pub fn zbar_symbol_get_type(symbol: *const zbar_symbol_t) -> zbar_symbol_type_t;
+    pub fn zbar_symbol_get_configs(symbol: *const zbar_symbol_t) -> c_uint;
+    pub fn zbar_symbol_get_modifiers(symbol: *const zbar_symbol_t) -> c_uint;
+    pub fn zbar_symbol_get_data(symbol: *const zbar_symbol_t) -> *const c_char;
+    pub fn zbar_symbol_get_data_length(symbol: *const zbar_symbol_t) -> c_uint;
+    pub fn zbar_symbol_get_quality(symbol: *const zbar_symbol_t) -> c_int;
+    pub fn zbar_symbol_get_count(symbol: *const zbar_symbol_t) -> c_int;
+    pub fn zbar_symbol_get_loc_size(symbol: *const zbar_symbol_t) -> c_uint;
+    pub fn zbar_symbol_get_loc_x(symbol: *const zbar_symbol_t, index: c_uint) -> c_int;
+    pub fn zbar_symbol_get_loc_y(symbol: *const zbar_symbol_t, index: c_uint) -> c_int;
+    pub fn zbar_symbol_get_orientation(symbol: *const zbar_symbol_t) -> zbar_orientation_t;
+    pub fn zbar_symbol_next(symbol: *const zbar_symbol_t) -> *const zbar_symbol_t;
+    pub fn zbar_symbol_get_components(symbol: *const zbar_symbol_t) -> *const zbar_symbol_set_t;
+    pub fn zbar_symbol_first_component(symbol: *const zbar_symbol_t) -> *const zbar_symbol_t;
+    pub fn zbar_symbol_xml(
+        symbol: *const zbar_symbol_t,
+        buffer: *mut *mut c_char,
+        buflen: *mut c_uint,
+    ) -> *mut c_char;
+}
+
+// TODO: ----- Symbol Interface END-----
+
+// TODO: ----- Symbol Set Interface START-----
+
+zbar_extern! {
+    pub fn zbar_symbol_set_ref(symbols: *const zbar_symbol_set_t, refs: c_int);
+    pub fn zbar_symbol_set_get_size(symbols: *const zbar_symbol_set_t) -> c_int;
+    pub fn zbar_symbol_set_first_symbol(symbols: *const zbar_symbol_set_t) -> *const zbar_symbol_t;
+    pub fn zbar_symbol_set_first_unfiltered(
+        symbols: *const zbar_symbol_set_t,
+    ) -> *const zbar_symbol_t;
+}
+
+// TODO: ----- Symbol Set Interface END-----
+
+// TODO: ----- Image Interface START-----
+
+zbar_extern! {
+    pub fn zbar_image_create() -> *mut zbar_image_t;
+    pub fn zbar_image_destroy(image: *mut zbar_image_t);
+    pub fn zbar_image_ref(image: *mut zbar_image_t, refs: c_int);
+    pub fn zbar_image_convert(image: *const zbar_image_t, format: c_ulong) -> *mut zbar_image_t;
+    pub fn zbar_image_convert_resize(
+        image: *const zbar_image_t,
+        format: c_ulong,
+        width: c_uint,
+        height: c_uint,
+    ) -> *mut zbar_image_t;
+    pub fn zbar_image_get_format(image: *const zbar_image_t) -> c_ulong;
+    pub fn zbar_image_get_sequence(image: *const zbar_image_t) -> c_uint;
+    pub fn zbar_image_get_width(image: *const zbar_image_t) -> c_uint;
+    pub fn zbar_image_get_height(image: *const zbar_image_t) -> c_uint;
+    pub fn zbar_image_get_size(image: *const zbar_image_t, width: *mut c_uint, height: *mut c_uint);
+    pub fn zbar_image_get_crop(
+        image: *const zbar_image_t,
+        x: *mut c_uint,
+        y: *mut c_uint,
+        width: *mut c_uint,
+        height: *mut c_uint,
+    );
+    pub fn zbar_image_get_data(image: *const zbar_image_t) -> *const c_void;
+    pub fn zbar_image_get_data_length(image: *const zbar_image_t) -> c_ulong;
+    pub fn zbar_image_get_symbols(image: *const zbar_image_t) -> *const zbar_symbol_set_t;
+    pub fn zbar_image_set_symbols(image: *mut zbar_image_t, symbols: *const zbar_symbol_set_t);
+    pub fn zbar_image_first_symbol(image: *const zbar_image_t) -> *const zbar_symbol_t;
+    pub fn zbar_image_set_format(image: *mut zbar_image_t, format: c_ulong);
+    pub fn zbar_image_set_sequence(image: *mut zbar_image_t, sequence_num: c_uint);
+    pub fn zbar_image_set_size(image: *mut zbar_image_t, width: c_uint, height: c_uint);
+    pub fn zbar_image_set_crop(
+        image: *mut zbar_image_t,
+        x: c_uint,
+        y: c_uint,
+        width: c_uint,
+        height: c_uint,
+    );
+    pub fn zbar_image_set_data(
+        image: *mut zbar_image_t,
+        data: *const c_void,
+        data_byte_length: c_ulong,
+        handler: Option<zbar_image_cleanup_handler_t>,
+    );
+    pub fn zbar_image_free_data(image: *mut zbar_image_t);
+    pub fn zbar_image_set_userdata(image: *mut zbar_image_t, userdata: *mut c_void);
+    pub fn zbar_image_get_userdata(image: *const zbar_image_t) -> *mut c_void;
+    pub fn zbar_image_write(image: *const zbar_image_t, filebase: *const c_char) -> c_int;
+    pub fn zbar_image_read(filename: *const c_char) -> *mut zbar_image_t;
+}
+
+// TODO: ----- Image Interface END-----
+
+// TODO: ----- Processor Interface START-----
+
+// not built by the `vendored` feature
+#[cfg(not(feature = "vendored"))]
+zbar_extern! {
+    pub fn zbar_processor_create(threaded: c_int) -> *mut zbar_processor_t;
+    pub fn zbar_processor_destroy(processor: *mut zbar_processor_t);
+    pub fn zbar_processor_init(
+        processor: *mut zbar_processor_t,
+        video_device: *const c_char,
+        enable_display: c_int,
+    ) -> c_int;
+    pub fn zbar_processor_request_size(
+        processor: *mut zbar_processor_t,
+        width: c_uint,
+        height: c_uint,
+    ) -> c_int;
+    pub fn zbar_processor_request_interface(processor: *mut zbar_processor_t, version: c_int) -> c_int;
+    pub fn zbar_processor_request_iomode(processor: *mut zbar_processor_t, iomode: c_int) -> c_int;
+    pub fn zbar_processor_force_format(
+        processor: *mut zbar_processor_t,
+        input_format: c_ulong,
+        output_format: c_ulong,
+    ) -> c_int;
+    pub fn zbar_processor_set_data_handler(
+        processor: *mut zbar_processor_t,
+        handler: Option<zbar_image_data_handler_t>,
+        userdata: *const c_void,
+    ) -> Option<zbar_image_data_handler_t>;
+    pub fn zbar_processor_set_userdata(processor: *mut zbar_processor_t, userdata: *mut c_void);
+    pub fn zbar_processor_get_userdata(processor: *const zbar_processor_t) -> *mut c_void;
+    pub fn zbar_processor_set_config(
+        processor: *mut zbar_processor_t,
+        symbology: zbar_symbol_type_t,
+        config: zbar_config_t,
+        value: c_int,
+    ) -> c_int;
+    #[cfg(zbar_0_22)]
+    pub fn zbar_processor_set_control(
+        processor: *mut zbar_processor_t,
+        control_name: *const c_char,
+        value: c_int,
+    ) -> c_int;
+    #[cfg(zbar_0_22)]
+    pub fn zbar_processor_get_control(
+        processor: *mut zbar_processor_t,
+        control_name: *const c_char,
+        value: *mut c_int,
+    ) -> c_int;
+    pub fn zbar_processor_get_results(processor: *const zbar_processor_t) -> *const zbar_symbol_set_t;
+    pub fn zbar_processor_is_visible(processor: *mut zbar_processor_t) -> c_int;
+    pub fn zbar_processor_set_visible(processor: *mut zbar_processor_t, visible: c_int) -> c_int;
+    pub fn zbar_processor_set_active(processor: *mut zbar_processor_t, active: c_int) -> c_int;
+    pub fn zbar_processor_user_wait(processor: *mut zbar_processor_t, timeout: c_int) -> c_int;
+    pub fn zbar_process_one(processor: *mut zbar_processor_t, timeout: c_int) -> c_int;
+    pub fn zbar_process_image(processor: *mut zbar_processor_t, image: *mut zbar_image_t) -> c_int;
+}
+
+// TODO: ----- Processor Interface END-----
+
+// TODO: ----- Video Interface START-----
+
+// not built by the `vendored` feature
+#[cfg(not(feature = "vendored"))]
+zbar_extern! {
+    pub fn zbar_video_create() -> *mut zbar_video_t;
+    pub fn zbar_video_destroy(video: *mut zbar_video_t);
+    pub fn zbar_video_open(video: *mut zbar_video_t, device: *const c_char) -> c_int;
+    pub fn zbar_video_get_fd(video: *const zbar_video_t) -> c_int;
+    pub fn zbar_video_request_size(video: *mut zbar_video_t, width: c_uint, height: c_uint) -> c_int;
+    pub fn zbar_video_request_interface(video: *mut zbar_video_t, version: c_int) -> c_int;
+    pub fn zbar_video_request_iomode(video: *mut zbar_video_t, iomode: c_int) -> c_int;
+    pub fn zbar_video_get_width(video: *const zbar_video_t) -> c_int;
+    pub fn zbar_video_get_height(video: *const zbar_video_t) -> c_int;
+    pub fn zbar_video_init(video: *mut zbar_video_t, format: c_ulong) -> c_int;
+    pub fn zbar_video_enable(video: *mut zbar_video_t, enable: c_int) -> c_int;
+    pub fn zbar_video_next_image(video: *mut zbar_video_t) -> *mut zbar_image_t;
+    #[cfg(zbar_0_22)]
+    pub fn zbar_video_set_control(
+        video: *mut zbar_video_t,
+        control_name: *const c_char,
+        value: c_int,
+    ) -> c_int;
+    #[cfg(zbar_0_22)]
+    pub fn zbar_video_get_control(
+        video: *mut zbar_video_t,
+        control_name: *const c_char,
+        value: *mut c_int,
+    ) -> c_int;
+    #[cfg(zbar_0_22)]
+    pub fn zbar_video_get_controls(video: *const zbar_video_t, index: c_int) -> *mut video_controls_t;
+    #[cfg(zbar_0_22)]
+    pub fn zbar_video_get_resolutions(
+        video: *const zbar_video_t,
+        index: c_int,
+    ) -> *mut video_resolution_t;
+}
+
+// TODO: ----- Video Interface END-----
+
+// TODO: ----- Window Interface START-----
+
+// not built by the `vendored` feature
+#[cfg(not(feature = "vendored"))]
+zbar_extern! {
+    pub fn zbar_window_create() -> *mut zbar_window_t;
+    pub fn zbar_window_destroy(window: *mut zbar_window_t);
+    pub fn zbar_window_attach(
+        window: *mut zbar_window_t,
+        x11_display_w32_hwnd: *mut c_void,
+        x11_drawable: c_ulong,
+    ) -> c_int;
+    pub fn zbar_window_set_overlay(window: *mut zbar_window_t, level: c_int);
+    pub fn zbar_window_get_overlay(window: *const zbar_window_t) -> c_int;
+    pub fn zbar_window_draw(window: *mut zbar_window_t, image: *mut zbar_image_t) -> c_int;
+    pub fn zbar_window_redraw(window: *mut zbar_window_t) -> c_int;
+    pub fn zbar_window_resize(window: *mut zbar_window_t, width: c_uint, height: c_uint) -> c_int;
+    pub fn zbar_negotiate_format(video: *mut zbar_video_t, window: *mut zbar_window_t) -> c_int;
+}
+
+// TODO: ----- Window Interface END-----
+
+// TODO: ----- Image Scanner Interface START-----
+
+zbar_extern! {
+    pub fn zbar_image_scanner_create() -> *mut zbar_image_scanner_t;
+    pub fn zbar_image_scanner_destroy(scanner: *mut zbar_image_scanner_t);
+    pub fn zbar_image_scanner_set_data_handler(
+        scanner: *mut zbar_image_scanner_t,
+        handler: Option<zbar_image_data_handler_t>,
+        userdata: *const c_void,
+    ) -> Option<zbar_image_data_handler_t>;
+    pub fn zbar_image_scanner_set_config(
+        scanner: *mut zbar_image_scanner_t,
+        symbology: zbar_symbol_type_t,
+        config: zbar_config_t,
+        value: c_int,
+    ) -> c_int;
+    #[cfg(zbar_0_22)]
+    pub fn zbar_image_scanner_get_config(
+        scanner: *const zbar_image_scanner_t,
+        symbology: zbar_symbol_type_t,
+        config: zbar_config_t,
+        value: *mut c_int,
+    ) -> c_int;
+    pub fn zbar_image_scanner_enable_cache(scanner: *mut zbar_image_scanner_t, enable: c_int);
+    pub fn zbar_image_scanner_recycle_image(
+        scanner: *mut zbar_image_scanner_t,
+        image: *mut zbar_image_t,
+    );
+    pub fn zbar_image_scanner_get_results(
+        scanner: *const zbar_image_scanner_t,
+    ) -> *const zbar_symbol_set_t;
+    pub fn zbar_scan_image(scanner: *mut zbar_image_scanner_t, image: *mut zbar_image_t) -> c_int;
+}
+
+// TODO: ----- Image Scanner Interface END-----
+
+// TODO: ----- Decoder Interface START-----
+
+zbar_extern! {
+    pub fn zbar_decoder_create() -> *mut zbar_decoder_t;
+    pub fn zbar_decoder_destroy(decoder: *mut zbar_decoder_t);
+    pub fn zbar_decoder_set_config(
+        decoder: *mut zbar_decoder_t,
+        symbology: zbar_symbol_type_t,
+        config: zbar_config_t,
+        value: c_int,
+    ) -> c_int;
+    #[cfg(zbar_0_22)]
+    pub fn zbar_decoder_get_config(
+        decoder: *mut zbar_decoder_t,
+        symbology: zbar_symbol_type_t,
+        config: zbar_config_t,
+        value: *mut c_int,
+    ) -> c_int;
+    pub fn zbar_decoder_get_configs(
+        decoder: *const zbar_decoder_t,
+        symbology: zbar_symbol_type_t,
+    ) -> c_uint;
+    pub fn zbar_decoder_reset(decoder: *mut zbar_decoder_t);
+    pub fn zbar_decoder_new_scan(decoder: *mut zbar_decoder_t);
+    pub fn zbar_decode_width(decoder: *mut zbar_decoder_t, width: c_uint) -> zbar_symbol_type_t;
+    pub fn zbar_decoder_get_color(decoder: *const zbar_decoder_t) -> zbar_color_t;
+    pub fn zbar_decoder_get_data(decoder: *const zbar_decoder_t) -> *const c_char;
+    pub fn zbar_decoder_get_data_length(decoder: *const zbar_decoder_t) -> c_uint;
+    pub fn zbar_decoder_get_type(decoder: *const zbar_decoder_t) -> zbar_symbol_type_t;
+    pub fn zbar_decoder_get_modifiers(decoder: *const zbar_decoder_t) -> c_uint;
+    pub fn zbar_decoder_get_direction(decoder: *const zbar_decoder_t) -> c_int;
+    pub fn zbar_decoder_set_handler(
+        decoder: *mut zbar_decoder_t,
+        handler: Option<zbar_decoder_handler_t>,
+    ) -> Option<zbar_decoder_handler_t>;
+    pub fn zbar_decoder_set_userdata(decoder: *mut zbar_decoder_t, userdata: *mut c_void);
+    pub fn zbar_decoder_get_userdata(decoder: *const zbar_decoder_t) -> *mut c_void;
+}
+
+// TODO: ----- Decoder Interface END-----
+
+// TODO: ----- Scanner Interface START-----
+
+zbar_extern! {
+    pub fn zbar_scanner_create(decoder: *mut zbar_decoder_t) -> *mut zbar_scanner_t;
+    pub fn zbar_scanner_destroy(scanner: *mut zbar_scanner_t);
+    pub fn zbar_scanner_reset(scanner: *mut zbar_scanner_t) -> zbar_symbol_type_t;
+    pub fn zbar_scanner_new_scan(scanner: *mut zbar_scanner_t) -> zbar_symbol_type_t;
+    pub fn zbar_scanner_flush(scanner: *mut zbar_scanner_t) -> zbar_symbol_type_t;
+    pub fn zbar_scan_y(scanner: *mut zbar_scanner_t, y: c_int) -> zbar_symbol_type_t;
+    pub fn zbar_scanner_get_width(scanner: *const zbar_scanner_t) -> c_uint;
+    pub fn zbar_scanner_get_edge(scanner: *const zbar_scanner_t, offset: c_uint, prec: c_int) -> c_uint;
+    pub fn zbar_scanner_get_color(scanner: *const zbar_scanner_t) -> zbar_color_t;
+}
+
+// TODO: ----- Scanner Interface END-----
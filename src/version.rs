@@ -0,0 +1,54 @@
+use libc::c_uint;
+
+use crate::{zbar_version, ZBarRustError};
+
+include!(concat!(env!("OUT_DIR"), "/version.rs"));
+
+/// The version of ZBar this crate was compiled against, as detected when it was built. When the version is found out from the header file, only the major and minor versions are known and the patch version is `0`.
+pub const COMPILED_VERSION: (u32, u32, u32) = DETECTED_VERSION;
+
+/// Get the version of the loaded ZBar library, as `(major, minor, patch)`. The patch version is `0` for ZBar older than 0.22, which does not report it.
+///
+/// # Panics
+///
+/// With the `dynamic-loading` feature, panics if the ZBar library cannot be loaded. Use `check_version` to handle that case.
+pub fn version() -> (u32, u32, u32) {
+    let mut major: c_uint = 0;
+    let mut minor: c_uint = 0;
+    let mut patch: c_uint = 0;
+
+    // older versions only take the first two arguments and ignore the third one
+    unsafe {
+        zbar_version(&mut major, &mut minor, &mut patch);
+    }
+
+    (major as u32, minor as u32, patch as u32)
+}
+
+/// Check that the loaded ZBar library provides the same APIs as the one this crate was compiled against, that is both versions reach the same ZBar releases which added APIs or changed values. Returns the version of the loaded library.
+///
+/// A mismatch means that some bindings do not fit the loaded library, for example `ZBarConfig` values changed in ZBar 0.23.
+pub fn check_version() -> Result<(u32, u32, u32), ZBarRustError> {
+    #[cfg(feature = "dynamic-loading")]
+    crate::load_library()?;
+
+    let loaded = version();
+
+    check_compatible(loaded).map(|_| loaded)
+}
+
+/// Check that a ZBar library of the `loaded` version reaches the same feature versions as the compiled one.
+pub(crate) fn check_compatible(loaded: (u32, u32, u32)) -> Result<(), ZBarRustError> {
+    let level = |(major, minor, _): (u32, u32, u32)| {
+        FEATURE_VERSIONS.iter().filter(|feature| (major, minor) >= **feature).count()
+    };
+
+    if level(loaded) == level(COMPILED_VERSION) {
+        Ok(())
+    } else {
+        Err(ZBarRustError::VersionMismatch {
+            compiled: COMPILED_VERSION,
+            loaded,
+        })
+    }
+}
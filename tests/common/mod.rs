@@ -0,0 +1,117 @@
+//! Renders the QR code and the EAN symbols which the tests scan.
+
+#![allow(dead_code)]
+
+const EAN_L_CODES: [&str; 10] = [
+    "0001101", "0011001", "0010011", "0111101", "0100011", "0110001", "0101111", "0111011",
+    "0110111", "0001011",
+];
+
+const EAN13_PARITIES: [&str; 10] = [
+    "LLLLLL", "LLGLGG", "LLGGLG", "LLGGGL", "LGLLGG", "LGGLLG", "LGGGLL", "LGLGLG", "LGLGGL",
+    "LGGLGL",
+];
+
+const EAN5_PARITIES: [&str; 10] =
+    ["GGLLL", "GLGLL", "GLLGL", "GLLLG", "LGGLL", "LLGGL", "LLLGG", "LGLGL", "LGLLG", "LLGLG"];
+
+fn digits(digits: &str) -> Vec<usize> {
+    digits.bytes().map(|b| (b - b'0') as usize).collect()
+}
+
+fn ean_digit(digit: usize, parity: char) -> String {
+    let l = EAN_L_CODES[digit];
+
+    match parity {
+        'L' => l.to_string(),
+        // G codes are the R codes reversed, and R codes are the complement of L codes
+        'G' => l.chars().rev().map(|c| if c == '0' { '1' } else { '0' }).collect(),
+        _ => l.chars().map(|c| if c == '0' { '1' } else { '0' }).collect(),
+    }
+}
+
+/// Render an EAN-13 symbol as modules (`'1'` is a bar).
+pub fn ean13_modules(ean13: &str) -> String {
+    let ean13 = digits(ean13);
+
+    let mut modules = String::from("101");
+
+    for (digit, parity) in ean13[1..7].iter().zip(EAN13_PARITIES[ean13[0]].chars()) {
+        modules.push_str(&ean_digit(*digit, parity));
+    }
+
+    modules.push_str("01010");
+
+    for digit in ean13[7..].iter() {
+        modules.push_str(&ean_digit(*digit, 'R'));
+    }
+
+    modules.push_str("101");
+
+    modules
+}
+
+/// Render an EAN-5 add-on as modules, without the gap which separates it from its symbol.
+pub fn ean5_modules(ean5: &str) -> String {
+    let ean5 = digits(ean5);
+
+    let checksum = (3 * (ean5[0] + ean5[2] + ean5[4]) + 9 * (ean5[1] + ean5[3])) % 10;
+
+    let mut modules = String::from("1011");
+
+    for (i, (digit, parity)) in ean5.iter().zip(EAN5_PARITIES[checksum].chars()).enumerate() {
+        if i > 0 {
+            modules.push_str("01");
+        }
+
+        modules.push_str(&ean_digit(*digit, parity));
+    }
+
+    modules
+}
+
+/// The widths of the elements of `modules`, in modules, starting and ending with a quiet zone of `quiet_zone` modules.
+pub fn widths(modules: &str, quiet_zone: u32) -> Vec<u32> {
+    let mut widths = vec![quiet_zone];
+    let mut previous = '0';
+
+    for module in modules.chars() {
+        if module == previous {
+            *widths.last_mut().unwrap() += 1;
+        } else {
+            widths.push(1);
+            previous = module;
+        }
+    }
+
+    if previous == '0' {
+        *widths.last_mut().unwrap() += quiet_zone;
+    } else {
+        widths.push(quiet_zone);
+    }
+
+    widths
+}
+
+/// Render `modules` as a row of gray pixels, with `module_width` pixels per module and quiet zones of 15 modules on both sides.
+pub fn row(modules: &str, module_width: usize) -> Vec<u8> {
+    let quiet_zone = vec![255u8; 15 * module_width];
+
+    let mut row = quiet_zone.clone();
+
+    for module in modules.chars() {
+        row.extend(std::iter::repeat(if module == '1' { 0 } else { 255 }).take(module_width));
+    }
+
+    row.extend(quiet_zone);
+
+    row
+}
+
+/// The text of the QR code rendered by `qr_code`.
+pub const URL: &str = "https://magiclen.org";
+
+/// Render `URL` as a QR code of `size` × `size` pixels, in the `Y800` format.
+pub fn qr_code(size: usize) -> Vec<u8> {
+    qrcode_generator::to_image_from_str(URL, qrcode_generator::QrCodeEcc::Low, size).unwrap()
+}
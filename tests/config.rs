@@ -0,0 +1,200 @@
+use zbar_rust::{
+    parse_config, ScannerConfig, ZBarConfig, ZBarImageScannerBuilder, ZBarRustError, ZBarSymbolType,
+};
+
+#[test]
+fn parse_config_strings() {
+    assert_eq!(
+        Ok((ZBarSymbolType::ZBarEAN13, ZBarConfig::ZBarCfgEnable, 0)),
+        parse_config("ean13.enable=0")
+    );
+    assert_eq!(
+        Ok((ZBarSymbolType::ZBarQRCode, ZBarConfig::ZBarCfgMinLen, 4)),
+        parse_config("qrcode.min-length=4")
+    );
+    assert_eq!(
+        Ok((ZBarSymbolType::ZBarNone, ZBarConfig::ZBarCfgEnable, 1)),
+        parse_config("enable")
+    );
+    assert_eq!(
+        Ok((ZBarSymbolType::ZBarNone, ZBarConfig::ZBarCfgEnable, 0)),
+        parse_config("*.disable")
+    );
+    assert_eq!(
+        Ok((ZBarSymbolType::ZBarCode128, ZBarConfig::ZBarCfgEnable, 1)),
+        parse_config("code128.no-disable")
+    );
+    assert_eq!(
+        Ok((ZBarSymbolType::ZBarI25, ZBarConfig::ZBarCfgAddCheck, 0)),
+        parse_config("i25.no-add-check")
+    );
+    assert_eq!(
+        Ok((ZBarSymbolType::ZBarDataBarExp, ZBarConfig::ZBarCfgXDensity, 16)),
+        parse_config("db-exp.x=0x10")
+    );
+    assert_eq!(
+        Ok((ZBarSymbolType::ZBarEAN13, ZBarConfig::ZBarCfgMaxLen, 8)),
+        parse_config("ean.max=010")
+    );
+    assert_eq!(
+        Ok((ZBarSymbolType::ZBarQRCode, ZBarConfig::ZBarCfgUncertainty, 0)),
+        parse_config("qr.uncertainty=0")
+    );
+    assert_eq!(
+        Ok((ZBarSymbolType::ZBarPartial, ZBarConfig::ZBarCfgEnable, -1)),
+        parse_config("scanner.en=-1")
+    );
+    assert_eq!(
+        Ok((ZBarSymbolType::ZBarNone, ZBarConfig::ZBarCfgPosition, 0)),
+        parse_config("posit=0")
+    );
+    #[cfg(zbar_0_23)]
+    assert_eq!(
+        Ok((ZBarSymbolType::ZBarSQCode, ZBarConfig::ZBarCfgEnable, 1)),
+        parse_config("sq.enable")
+    );
+}
+
+#[test]
+fn parse_config_empty_values() {
+    // values without digits are 0, like in `zbar_parse_config`
+    for config in ["min-length=", "min-length=-", "min-length=0x", "min-length=-0x"] {
+        assert_eq!(
+            Ok((ZBarSymbolType::ZBarNone, ZBarConfig::ZBarCfgMinLen, 0)),
+            parse_config(config)
+        );
+    }
+}
+
+#[test]
+fn parse_config_errors() {
+    let error = |position, reason| {
+        Err(ZBarRustError::InvalidConfigString {
+            position,
+            reason,
+        })
+    };
+
+    assert_eq!(error(0, "unknown symbology"), parse_config("q.enable"));
+    assert_eq!(error(0, "unknown symbology"), parse_config("code.enable"));
+    assert_eq!(error(6, "unknown config"), parse_config("ean13.e"));
+    assert_eq!(error(9, "unknown config"), parse_config("ean13.no-foo"));
+    assert_eq!(error(0, "unknown config"), parse_config("pos"));
+    assert_eq!(error(0, "unknown config"), parse_config("di"));
+    assert_eq!(error(6, "missing config name"), parse_config("ean13.=1"));
+    assert_eq!(error(12, "invalid value"), parse_config("min-length=4x"));
+    assert_eq!(error(12, "invalid value"), parse_config("min-length=08"));
+    assert_eq!(error(11, "value out of range"), parse_config("min-length=4294967296"));
+}
+
+#[test]
+fn scanner_config() {
+    let mut config = ScannerConfig::new();
+
+    config.set(ZBarSymbolType::ZBarQRCode, ZBarConfig::ZBarCfgEnable, 1);
+    config.set(ZBarSymbolType::ZBarNone, ZBarConfig::ZBarCfgEnable, 0);
+    config.set(ZBarSymbolType::ZBarQRCode, ZBarConfig::ZBarCfgEnable, 1);
+    config.set(ZBarSymbolType::ZBarCode128, ZBarConfig::ZBarCfgMinLen, 4);
+    config.set(ZBarSymbolType::ZBarCode128, ZBarConfig::ZBarCfgMinLen, 6);
+
+    assert_eq!(3, config.len());
+    assert_eq!(Some(1), config.get(ZBarSymbolType::ZBarQRCode, ZBarConfig::ZBarCfgEnable));
+    assert_eq!(Some(0), config.get(ZBarSymbolType::ZBarEAN13, ZBarConfig::ZBarCfgEnable));
+    assert_eq!(Some(6), config.get(ZBarSymbolType::ZBarCode128, ZBarConfig::ZBarCfgMinLen));
+    assert_eq!(None, config.get(ZBarSymbolType::ZBarCode128, ZBarConfig::ZBarCfgMaxLen));
+
+    let s = config.to_config_string().unwrap();
+
+    assert_eq!("enable=0 qrcode.enable=1 code128.min-length=6", s);
+    assert_eq!(Ok(config), s.parse());
+
+    assert_eq!(
+        Err(ZBarRustError::InvalidConfigString {
+            position: 12, reason: "unknown config"
+        }),
+        "disable  qr.foo".parse::<ScannerConfig>()
+    );
+
+    // settings which have no name in config strings are not written
+    let mut config = ScannerConfig::new();
+
+    config.set(ZBarSymbolType::ZBarAddOn2, ZBarConfig::ZBarCfgEnable, 1);
+
+    assert_eq!(
+        Err(ZBarRustError::InvalidConfig {
+            symbology: ZBarSymbolType::ZBarAddOn2,
+            config:    ZBarConfig::ZBarCfgEnable,
+            value:     1,
+        }),
+        config.to_config_string()
+    );
+
+    #[cfg(zbar_0_23)]
+    {
+        let mut config = ScannerConfig::new();
+
+        config.set(ZBarSymbolType::ZBarSQCode, ZBarConfig::ZBarCfgEnable, 1);
+        config.set(ZBarSymbolType::ZBarNone, ZBarConfig::ZBarCfgTestInverted, 1);
+
+        let s = config.to_config_string().unwrap();
+
+        assert_eq!("sqcode.enable=1 test-inverted=1", s);
+        assert_eq!(Ok(config), s.parse());
+    }
+}
+
+#[test]
+fn scanner_builder() {
+    let config = ZBarImageScannerBuilder::new()
+        .symbologies([ZBarSymbolType::ZBarQRCode, ZBarSymbolType::ZBarEAN13])
+        .enable(ZBarSymbolType::ZBarCode128)
+        .disable(ZBarSymbolType::ZBarEAN13)
+        .min_length(ZBarSymbolType::ZBarCode128, 4)
+        .max_length(ZBarSymbolType::ZBarCode128, 16)
+        .density(2, 2)
+        .to_config()
+        .unwrap();
+
+    assert_eq!(
+        "enable=0 qrcode.enable=1 code128.enable=1 code128.min-length=4 code128.max-length=16 \
+         x-density=2 y-density=2",
+        config.to_config_string().unwrap()
+    );
+
+    let conflict = |symbology, reason| {
+        Err(ZBarRustError::ConflictingConfig {
+            symbology,
+            reason,
+        })
+    };
+
+    assert_eq!(
+        conflict(ZBarSymbolType::ZBarQRCode, "the symbology has no length limits"),
+        ZBarImageScannerBuilder::new().min_length(ZBarSymbolType::ZBarQRCode, 4).to_config()
+    );
+    assert_eq!(
+        conflict(
+            ZBarSymbolType::ZBarCode39,
+            "the minimum length is greater than the maximum length"
+        ),
+        ZBarImageScannerBuilder::new()
+            .min_length(ZBarSymbolType::ZBarCode39, 8)
+            .max_length(ZBarSymbolType::ZBarCode39, 4)
+            .to_config()
+    );
+    assert_eq!(
+        conflict(ZBarSymbolType::ZBarCode39, "the symbology is not enabled"),
+        ZBarImageScannerBuilder::new()
+            .enable(ZBarSymbolType::ZBarQRCode)
+            .add_check(ZBarSymbolType::ZBarCode39, true)
+            .to_config()
+    );
+    assert_eq!(
+        conflict(ZBarSymbolType::ZBarNone, "no symbology is enabled"),
+        ZBarImageScannerBuilder::new().symbologies([]).to_config()
+    );
+    assert_eq!(
+        conflict(ZBarSymbolType::ZBarNone, "both scan densities are zero"),
+        ZBarImageScannerBuilder::new().density(0, 0).to_config()
+    );
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use zbar_rust::{ZBarConfig, ZBarDecoder, ZBarSymbolType};
+
+#[test]
+fn decode_widths() {
+    let mut decoder = ZBarDecoder::new();
+
+    let mut decoded = Vec::new();
+
+    for width in common::widths(&common::ean13_modules("9780306406157"), 10) {
+        // ZBar works with widths in 1/32 pixel
+        match decoder.decode_width(width * 32 * 2) {
+            ZBarSymbolType::ZBarNone | ZBarSymbolType::ZBarPartial => (),
+            symbol_type => {
+                assert_eq!(symbol_type, decoder.symbol_type());
+
+                decoded.push((symbol_type, decoder.data().to_vec()));
+            },
+        }
+    }
+
+    assert_eq!(vec![(ZBarSymbolType::ZBarEAN13, b"9780306406157".to_vec())], decoded);
+
+    // the color alternates with every width
+    let color = decoder.color();
+
+    decoder.decode_width(64);
+
+    assert_ne!(color, decoder.color());
+
+    decoder.new_scan();
+    decoder.reset();
+}
+
+#[test]
+fn decoder_config() {
+    let mut decoder = ZBarDecoder::new();
+
+    assert!(decoder.configs(ZBarSymbolType::ZBarEAN13).contains(ZBarConfig::ZBarCfgEnable));
+
+    decoder.set_config(ZBarSymbolType::ZBarEAN13, ZBarConfig::ZBarCfgEnable, 0).unwrap();
+
+    assert!(!decoder.configs(ZBarSymbolType::ZBarEAN13).contains(ZBarConfig::ZBarCfgEnable));
+
+    #[cfg(zbar_0_22)]
+    assert_eq!(Ok(0), decoder.get_config(ZBarSymbolType::ZBarEAN13, ZBarConfig::ZBarCfgEnable));
+
+    for width in common::widths(&common::ean13_modules("9780306406157"), 10) {
+        let symbol_type = decoder.decode_width(width * 64);
+
+        assert!(symbol_type != ZBarSymbolType::ZBarEAN13);
+    }
+}
@@ -0,0 +1,44 @@
+use zbar_rust::{FourCC, FourCCLayout, FourCCPlane};
+
+#[test]
+fn fourcc() {
+    assert_eq!(FourCC::Y800, FourCC::from(*b"Y800"));
+    assert_eq!(808_466_521, u32::from(FourCC::Y800));
+    assert_eq!(FourCC::GREY, FourCC::from(1_497_715_271));
+    assert_eq!("Y800", FourCC::Y800.to_string());
+    assert_eq!("Y8", FourCC::Y8_NUL.to_string());
+    assert_eq!("0x00000001", FourCC::from_u32(1).to_string());
+
+    assert_eq!(Some(FourCCLayout::Gray), FourCC::GREY.layout());
+    assert_eq!(None, FourCC::from(*b"ABCD").layout());
+    assert_eq!(Some(FourCCLayout::Jpeg), FourCC::from(*b"MJPG").layout());
+    assert_eq!(Some(1), FourCC::BGR1.bytes_per_pixel());
+    assert_eq!(Some(16), FourCC::R444.bits_per_pixel());
+    assert_eq!(Some(4), FourCC::from_u32(3).bytes_per_pixel());
+    assert_eq!(
+        Some(FourCCLayout::RgbPacked {
+            bytes_per_pixel: 4
+        }),
+        FourCC::BI_BITFIELDS.layout()
+    );
+
+    assert_eq!(Some(12), FourCC::I420.bits_per_pixel());
+    assert_eq!(Some(16), FourCC::YUYV.bits_per_pixel());
+    assert_eq!(Some(3), FourCC::RGB3.bytes_per_pixel());
+    assert_eq!(None, FourCC::NV12.bytes_per_pixel());
+
+    assert_eq!(
+        Some(vec![
+            FourCCPlane {
+                row_bytes: 5, rows: 3
+            },
+            FourCCPlane {
+                row_bytes: 6, rows: 2
+            },
+        ]),
+        FourCC::NV12.planes(5, 3)
+    );
+    assert_eq!(Some(5 * 3 + 3 * 2 * 2), FourCC::I420.data_length(5, 3));
+    assert_eq!(Some(6 * 2 * 3), FourCC::YUYV.data_length(5, 3));
+    assert_eq!(None, FourCC::JPEG.data_length(5, 3));
+}
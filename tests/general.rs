@@ -9,3 +9,24 @@ fn version() {
 
     assert!(major == 0 && minor >= 10);
 }
+
+#[test]
+fn safe_version() {
+    let (major, minor, _) = zbar_rust::version();
+
+    assert!(major == 0 && minor >= 10);
+
+    assert_eq!(Ok(zbar_rust::version()), zbar_rust::check_version());
+}
+
+#[cfg(feature = "dynamic-loading")]
+#[test]
+fn dynamic_loading() {
+    zbar_rust::load_library().unwrap();
+
+    // loading again keeps the library loaded at first
+    zbar_rust::load_library().unwrap();
+    zbar_rust::load_library_from("/nonexistent/libzbar.so.0").unwrap();
+
+    assert!(zbar_rust::ZBarImageScanner::try_new().is_ok());
+}
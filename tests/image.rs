@@ -1,6 +1,172 @@
-use zbar_rust::ZBarImage;
+use zbar_rust::{FourCC, ZBarImage, ZBarImageScanner, ZBarRegion, ZBarRustError};
 
 #[test]
 fn image_create_destroy() {
     let _zbar = ZBarImage::new();
 }
+
+#[test]
+fn image_crop() {
+    let mut image = ZBarImage::new();
+
+    image.set_size(640, 480).unwrap();
+
+    assert_eq!(ZBarRegion::new(0, 0, 640, 480), image.crop());
+
+    image.set_crop(ZBarRegion::new(10, 20, 100, 200));
+
+    assert_eq!(ZBarRegion::new(10, 20, 100, 200), image.crop());
+}
+
+#[test]
+fn image_from_borrowed() {
+    let data = vec![128u8; 32 * 16];
+
+    let mut image = ZBarImage::from_borrowed(&data, 32, 16, FourCC::Y800).unwrap();
+
+    assert_eq!(32, image.width());
+    assert_eq!(16, image.height());
+    assert_eq!(FourCC::Y800, image.format());
+    assert_eq!(data.as_slice(), image.data());
+
+    image.set_sequence(7);
+
+    assert_eq!(7, image.sequence());
+
+    assert_eq!(
+        Err(ZBarRustError::BufferSizeMismatch {
+            expected: 32 * 32, actual: 32 * 16
+        }),
+        image.set_size(32, 32)
+    );
+
+    assert_eq!(
+        ZBarRustError::BufferSizeMismatch {
+            expected: 32 * 16, actual: 32 * 15
+        },
+        ZBarImage::from_borrowed(&data[..32 * 15], 32, 16, FourCC::Y800).err().unwrap()
+    );
+}
+
+#[test]
+fn image_from_owned() {
+    let data = vec![128u8; 32 * 16];
+
+    let image = ZBarImage::from_owned(data.clone(), 32, 16, FourCC::Y800).unwrap();
+
+    assert_eq!(data.as_slice(), image.data());
+
+    drop(image);
+}
+
+#[test]
+fn image_convert() {
+    let luma = (0..32 * 16).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+    let yuyv = luma.iter().flat_map(|y| [*y, 128]).collect::<Vec<u8>>();
+
+    let image = ZBarImage::from_borrowed(&yuyv, 32, 16, FourCC::YUYV).unwrap();
+
+    let converted = image.convert(FourCC::Y800).unwrap();
+
+    drop(image);
+
+    assert_eq!(32, converted.width());
+    assert_eq!(16, converted.height());
+    assert_eq!(FourCC::Y800, converted.format());
+    assert_eq!(luma.as_slice(), converted.data());
+
+    let resized = converted.convert_resize(FourCC::Y800, 16, 8).unwrap();
+
+    assert_eq!(16, resized.width());
+    assert_eq!(8, resized.height());
+    assert_eq!(16 * 8, resized.data().len());
+    assert_eq!(&luma[..16], &resized.data()[..16]);
+
+    assert_eq!(ZBarRustError::NoImageData, ZBarImage::new().convert(FourCC::Y800).err().unwrap());
+}
+
+#[test]
+fn image_convert_shared() {
+    let luma = (0..16 * 8).map(|i| i as u8).collect::<Vec<u8>>();
+
+    // ZBar does not copy the pixels if the format stays the same, but the converted image must outlive the borrowed data
+    let converted = {
+        let data = luma.clone();
+
+        let image = ZBarImage::from_borrowed(&data, 16, 8, FourCC::Y800).unwrap();
+
+        image.convert(FourCC::GREY).unwrap()
+    };
+
+    assert_eq!(FourCC::GREY, converted.format());
+    assert_eq!(luma.as_slice(), converted.data());
+
+    let same = {
+        let data = luma.clone();
+
+        let image = ZBarImage::from_borrowed(&data, 16, 8, FourCC::Y800).unwrap();
+
+        image.convert_resize(FourCC::Y800, 16, 8).unwrap()
+    };
+
+    assert_eq!(luma.as_slice(), same.data());
+
+    // the luma plane of a planar YUV image is kept, without the chroma planes
+    let mut converted = {
+        let i420 = luma.iter().copied().chain([128; 2 * 8 * 4]).collect::<Vec<u8>>();
+
+        let image = ZBarImage::from_borrowed(&i420, 16, 8, FourCC::I420).unwrap();
+
+        image.convert(FourCC::Y800).unwrap()
+    };
+
+    assert_eq!(FourCC::Y800, converted.format());
+    assert_eq!(luma.as_slice(), converted.data());
+
+    assert_eq!(Ok(0), ZBarImageScanner::new().scan_image(&mut converted));
+}
+
+#[test]
+fn image_from_strided() {
+    let data = (0..16 * 8).map(|i| i as u8).collect::<Vec<u8>>();
+
+    let padded = data
+        .chunks_exact(16)
+        .flat_map(|row| row.iter().copied().chain([0; 8]))
+        .collect::<Vec<u8>>();
+
+    let image = ZBarImage::from_borrowed_strided(&padded, 16, 8, FourCC::Y800, &[(0, 24)]).unwrap();
+
+    assert_eq!(data.as_slice(), image.data());
+
+    drop(image);
+
+    let image = ZBarImage::from_owned_strided(padded, 16, 8, FourCC::Y800, &[(0, 24)]).unwrap();
+
+    assert_eq!(data.as_slice(), image.data());
+
+    let image = ZBarImage::from_borrowed_strided(&data, 16, 8, FourCC::Y800, &[(0, 16)]).unwrap();
+
+    assert_eq!(data.as_ptr(), image.data().as_ptr());
+
+    // an I420 frame whose Y plane is allocated with 2 more rows, and whose chroma planes start at 64-byte boundaries
+    let mut aligned = vec![0u8; 256 + 32];
+
+    aligned[..16 * 8].copy_from_slice(&data);
+    aligned[192..192 + 32].fill(1);
+    aligned[256..256 + 32].fill(2);
+
+    let image = ZBarImage::from_borrowed_strided(&aligned, 16, 8, FourCC::I420, &[
+        (0, 16),
+        (192, 8),
+        (256, 8),
+    ])
+    .unwrap();
+
+    let mut expected = data.clone();
+
+    expected.extend([1; 8 * 4]);
+    expected.extend([2; 8 * 4]);
+
+    assert_eq!(expected.as_slice(), image.data());
+}
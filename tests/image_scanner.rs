@@ -1,5 +1,15 @@
-use qrcode_generator::QrCodeEcc;
-use zbar_rust::{ZBarConfig, ZBarImageScanner, ZBarSymbolType};
+mod common;
+
+use std::{
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+    rc::Rc,
+};
+
+use zbar_rust::{
+    FourCC, ZBarConfig, ZBarImage, ZBarImageScanResult, ZBarImageScanner, ZBarRegion,
+    ZBarRustError, ZBarSymbolType,
+};
 
 #[test]
 fn image_create_destroy() {
@@ -17,11 +27,9 @@ fn set_config() {
 fn decode_qrcode() {
     let mut scanner = ZBarImageScanner::new();
 
-    let url = "https://magiclen.org";
-
     let size = 512;
 
-    let data = qrcode_generator::to_image_from_str(url, QrCodeEcc::Low, size).unwrap();
+    let data = common::qr_code(size);
 
     let mut result = scanner.scan_y800(&data, size as u32, size as u32).unwrap();
 
@@ -31,5 +39,389 @@ fn decode_qrcode() {
     assert_eq!(34, result[0].points.iter().map(|(_, y)| *y).min().unwrap()); // top
     assert_eq!(479, result[0].points.iter().map(|(x, _)| *x).max().unwrap()); // right
     assert_eq!(479, result[0].points.iter().map(|(_, y)| *y).max().unwrap()); // bottom
-    assert_eq!(url, unsafe { String::from_utf8_unchecked(result.remove(0).data) });
+    assert_eq!(common::URL, unsafe { String::from_utf8_unchecked(result.remove(0).data) });
+}
+
+#[test]
+fn set_config_error() {
+    let mut scanner = ZBarImageScanner::new();
+
+    assert_eq!(
+        Err(ZBarRustError::InvalidConfig {
+            symbology: ZBarSymbolType::ZBarEAN13,
+            config:    ZBarConfig::ZBarCfgMinLen,
+            value:     1,
+        }),
+        scanner.set_config(ZBarSymbolType::ZBarEAN13, ZBarConfig::ZBarCfgMinLen, 1)
+    );
+}
+
+#[test]
+fn scan_buffer_size_mismatch() {
+    let mut scanner = ZBarImageScanner::new();
+
+    let data = vec![0u8; 100 * 99];
+
+    assert_eq!(
+        Err(ZBarRustError::BufferSizeMismatch {
+            expected: 100 * 100, actual: 100 * 99
+        }),
+        scanner.scan_y800(&data, 100, 100).map(|_| ())
+    );
+}
+
+#[test]
+fn scan_region() {
+    let mut scanner = ZBarImageScanner::new();
+
+    let size = 512;
+
+    let qrcode = common::qr_code(size);
+
+    // put the QR code on the right half of a frame which is twice as wide
+    let width = size * 2;
+
+    let mut data = vec![255u8; width * size];
+
+    for (y, row) in qrcode.chunks_exact(size).enumerate() {
+        data[y * width + size..(y + 1) * width].copy_from_slice(row);
+    }
+
+    let result = scanner
+        .scan_region(
+            &data,
+            width as u32,
+            size as u32,
+            FourCC::Y800,
+            ZBarRegion::new(0, 0, 512, 512),
+        )
+        .unwrap();
+
+    assert!(result.is_empty());
+
+    let result = scanner
+        .scan_region(
+            &data,
+            width as u32,
+            size as u32,
+            FourCC::Y800,
+            ZBarRegion::new(512, 0, 512, 512),
+        )
+        .unwrap();
+
+    assert_eq!(1, result.len());
+    assert_eq!(546, result[0].points.iter().map(|(x, _)| *x).min().unwrap()); // left
+    assert_eq!(991, result[0].points.iter().map(|(x, _)| *x).max().unwrap()); // right
+
+    let region = ZBarRegion::new(600, 0, 512, 512);
+
+    assert_eq!(
+        Err(ZBarRustError::RegionOutOfBounds {
+            region,
+            width: width as u32,
+            height: size as u32,
+        }),
+        scanner.scan_region(&data, width as u32, size as u32, FourCC::Y800, region).map(|_| ())
+    );
+}
+
+#[test]
+fn scan_image() {
+    let mut scanner = ZBarImageScanner::new();
+
+    let size = 512;
+
+    let data = common::qr_code(size);
+
+    let mut image = ZBarImage::from_owned(data, size as u32, size as u32, FourCC::Y800).unwrap();
+
+    assert_eq!(1, scanner.scan_image(&mut image).unwrap());
+    assert_eq!(common::URL.as_bytes(), image.symbols().next().unwrap().data());
+
+    // an image without data is not passed to ZBar
+    let mut image = ZBarImage::new();
+
+    image.set_size(size as u32, size as u32).unwrap();
+    image.set_format(FourCC::Y800).unwrap();
+
+    assert_eq!(Err(ZBarRustError::NoImageData), scanner.scan_image(&mut image));
+}
+
+#[test]
+fn scan_color_formats() {
+    let mut scanner = ZBarImageScanner::new();
+
+    let size = 256;
+
+    let luma = common::qr_code(size);
+
+    let size = size as u32;
+
+    let check = |results: Vec<ZBarImageScanResult>| {
+        assert_eq!(1, results.len());
+        assert_eq!(common::URL.as_bytes(), results[0].data.as_slice());
+    };
+
+    let rgb = luma.iter().flat_map(|y| [*y, *y, *y]).collect::<Vec<u8>>();
+    check(scanner.scan_rgb8(&rgb, size, size).unwrap());
+    check(scanner.scan_bgr8(&rgb, size, size).unwrap());
+
+    let rgba = luma.iter().flat_map(|y| [*y, *y, *y, 255]).collect::<Vec<u8>>();
+    check(scanner.scan_rgba8(&rgba, size, size).unwrap());
+
+    let yuyv = luma.iter().flat_map(|y| [*y, 128]).collect::<Vec<u8>>();
+    check(scanner.scan_yuyv(&yuyv, size, size).unwrap());
+
+    let mut yuv420 = luma.clone();
+    yuv420.resize(luma.len() * 3 / 2, 128);
+    check(scanner.scan_nv12(&yuv420, size, size).unwrap());
+    check(scanner.scan_i420(&yuv420, size, size).unwrap());
+
+    assert_eq!(
+        Err(ZBarRustError::BufferSizeMismatch {
+            expected: luma.len() * 3 / 2,
+            actual:   luma.len(),
+        }),
+        scanner.scan_i420(&luma, size, size).map(|_| ())
+    );
+}
+
+#[test]
+fn scan_strided() {
+    let mut scanner = ZBarImageScanner::new();
+
+    let size = 256;
+
+    let luma = common::qr_code(size);
+
+    let size = size as u32;
+
+    let check = |results: Vec<ZBarImageScanResult>| {
+        assert_eq!(1, results.len());
+        assert_eq!(common::URL.as_bytes(), results[0].data.as_slice());
+    };
+
+    let pad = |data: &[u8], row_bytes: usize, stride: usize| {
+        data.chunks_exact(row_bytes)
+            .flat_map(|row| {
+                row.iter().copied().chain(std::iter::repeat(0).take(stride - row_bytes))
+            })
+            .collect::<Vec<u8>>()
+    };
+
+    let padded = pad(&luma, 256, 320);
+    check(scanner.scan_strided(&padded, size, size, FourCC::Y800, &[(0, 320)]).unwrap());
+
+    let rgba = luma.iter().flat_map(|y| [*y, *y, *y, 255]).collect::<Vec<u8>>();
+    check(scanner.scan_rgba8_strided(pad(&rgba, 1024, 1088), size, size, 1088).unwrap());
+
+    let mut i420 = pad(&luma, 256, 320);
+    i420.resize(i420.len() + 2 * 160 * 128, 128);
+    check(
+        scanner
+            .scan_i420_strided(&i420, size, size, [
+                (0, 320),
+                (320 * 256, 160),
+                (320 * 256 + 160 * 128, 160),
+            ])
+            .unwrap(),
+    );
+
+    // the Y plane is allocated with 16 more rows, like 1088 rows for a 1080p frame
+    let mut nv12 = pad(&luma, 256, 320);
+    nv12.resize(320 * 272 + 320 * 128, 128);
+    check(scanner.scan_nv12_strided(&nv12, size, size, [(0, 320), (320 * 272, 320)]).unwrap());
+
+    assert_eq!(
+        Err(ZBarRustError::BufferSizeMismatch {
+            expected: 320 * 272 + 320 * 127 + 256,
+            actual:   320 * 272 + 320 * 127,
+        }),
+        scanner
+            .scan_nv12_strided(&nv12[..320 * 272 + 320 * 127], size, size, [
+                (0, 320),
+                (320 * 272, 320)
+            ])
+            .map(|_| ())
+    );
+
+    assert_eq!(
+        Err(ZBarRustError::StrideTooSmall {
+            plane: 0, stride: 255, row_bytes: 256
+        }),
+        scanner.scan_strided(&padded, size, size, FourCC::Y800, &[(0, 255)]).map(|_| ())
+    );
+
+    assert_eq!(
+        Err(ZBarRustError::StrideCountMismatch {
+            expected: 3, actual: 1
+        }),
+        scanner.scan_strided(&i420, size, size, FourCC::I420, &[(0, 320)]).map(|_| ())
+    );
+}
+
+#[test]
+fn parse_config() {
+    let mut scanner = ZBarImageScanner::new();
+
+    scanner.parse_config("disable").unwrap();
+    scanner.parse_config("qrcode.enable").unwrap();
+    // QR codes have no length limits, which ZBar rejects
+    scanner.parse_config("code128.min-length=4").unwrap();
+
+    assert_eq!(Ok(4), scanner.get_config(ZBarSymbolType::ZBarCode128, ZBarConfig::ZBarCfgMinLen));
+
+    assert!(matches!(
+        scanner.parse_config("qrcode.foo"),
+        Err(ZBarRustError::InvalidConfigString {
+            position: 7,
+            ..
+        })
+    ));
+
+    // the parser agrees with ZBar, including the shortest abbreviations
+    for config in [
+        "ean13.enable=0",
+        "qrcode.min-length=4",
+        "*.disable",
+        "i25.no-add-check",
+        "db-exp.x=0x10",
+        "pos",
+        "posit",
+        "d",
+        "di",
+        "dis",
+        "e",
+        "en",
+        "no-",
+        "q.enable",
+        "qr.enable",
+        "ean.enable",
+        "code.enable",
+        "code128.min-length=",
+        "code128.min-length=-",
+        "code128.min-length=0x",
+        "code128.min-length=-0x",
+        "code128.min-length=0x1F",
+        "code128.min-length=010",
+    ] {
+        let parsed = zbar_rust::parse_config(config);
+
+        let c_config = std::ffi::CString::new(config).unwrap();
+
+        let (mut zbar_symbology, mut zbar_config, mut zbar_value) = (0, 0, 0);
+
+        let result = unsafe {
+            zbar_rust::zbar_parse_config(
+                c_config.as_ptr(),
+                &mut zbar_symbology,
+                &mut zbar_config,
+                &mut zbar_value,
+            )
+        };
+
+        match parsed {
+            Ok((symbology, cfg, value)) => {
+                assert_eq!(0, result, "ZBar rejects {config:?}");
+                assert_eq!(symbology.to_raw(), zbar_symbology);
+                assert_eq!(cfg as i32, zbar_config);
+                assert_eq!(value as i32, zbar_value);
+            },
+            Err(_) => assert_ne!(0, result, "ZBar accepts {config:?}"),
+        }
+    }
+}
+
+#[test]
+fn config_snapshot() {
+    let mut scanner = ZBarImageScanner::new();
+
+    scanner.set_config(ZBarSymbolType::ZBarNone, ZBarConfig::ZBarCfgEnable, 0).unwrap();
+    scanner.set_config(ZBarSymbolType::ZBarQRCode, ZBarConfig::ZBarCfgEnable, 1).unwrap();
+    scanner.parse_config("code128.min-length=4").unwrap();
+
+    assert_eq!(Ok(1), scanner.get_config(ZBarSymbolType::ZBarQRCode, ZBarConfig::ZBarCfgEnable));
+    assert_eq!(Ok(0), scanner.get_config(ZBarSymbolType::ZBarEAN13, ZBarConfig::ZBarCfgEnable));
+    assert_eq!(Ok(4), scanner.get_config(ZBarSymbolType::ZBarCode128, ZBarConfig::ZBarCfgMinLen));
+
+    let config = scanner.config();
+
+    assert_eq!("enable=0 qrcode.enable=1 code128.min-length=4", config.to_config_string().unwrap());
+
+    let mut other = ZBarImageScanner::new();
+
+    other.apply_config(&config).unwrap();
+
+    assert_eq!(config, other.config());
+    assert_eq!(Ok(0), other.get_config(ZBarSymbolType::ZBarEAN13, ZBarConfig::ZBarCfgEnable));
+}
+
+#[test]
+fn scanner_builder() {
+    let size = 512;
+
+    let data = common::qr_code(size);
+
+    let mut scanner = ZBarImageScanner::builder()
+        .symbologies([ZBarSymbolType::ZBarQRCode])
+        .density(2, 2)
+        .build()
+        .unwrap();
+
+    assert_eq!(Ok(0), scanner.get_config(ZBarSymbolType::ZBarEAN13, ZBarConfig::ZBarCfgEnable));
+    assert_eq!(Ok(2), scanner.get_config(ZBarSymbolType::ZBarNone, ZBarConfig::ZBarCfgXDensity));
+    assert_eq!(Ok(2), scanner.get_config(ZBarSymbolType::ZBarPartial, ZBarConfig::ZBarCfgYDensity));
+
+    let result = scanner.scan_y800(&data, size as u32, size as u32).unwrap();
+
+    assert_eq!(1, result.len());
+    assert_eq!(common::URL.as_bytes(), result[0].data.as_slice());
+
+    let mut scanner =
+        ZBarImageScanner::builder().enable(ZBarSymbolType::ZBarEAN13).build().unwrap();
+
+    assert!(scanner.scan_y800(&data, size as u32, size as u32).unwrap().is_empty());
+}
+
+#[test]
+fn on_decode() {
+    let size = 512;
+
+    let data = common::qr_code(size);
+
+    let decoded = Rc::new(RefCell::new(Vec::new()));
+
+    let mut scanner = ZBarImageScanner::new();
+
+    {
+        let decoded = decoded.clone();
+
+        scanner.on_decode(move |symbol| {
+            decoded.borrow_mut().push((symbol.symbol_type(), symbol.data().to_vec()))
+        });
+    }
+
+    let result = scanner.scan_y800(&data, size as u32, size as u32).unwrap();
+
+    assert_eq!(1, result.len());
+    assert_eq!(
+        vec![(ZBarSymbolType::ZBarQRCode, common::URL.as_bytes().to_vec())],
+        *decoded.borrow()
+    );
+
+    // a panic of the callback is resumed after the scan
+    scanner.on_decode(|_| panic!("on_decode"));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        scanner.scan_y800(&data, size as u32, size as u32)
+    }));
+
+    assert_eq!(Some(&"on_decode"), result.unwrap_err().downcast_ref::<&str>());
+
+    // the replaced callback has been dropped
+    assert_eq!(1, Rc::strong_count(&decoded));
+
+    scanner.remove_on_decode();
+
+    assert_eq!(1, scanner.scan_y800(&data, size as u32, size as u32).unwrap().len());
 }
@@ -0,0 +1,59 @@
+#![cfg(feature = "image")]
+
+mod common;
+
+use image::{DynamicImage, GenericImageView, GrayImage};
+use zbar_rust::ZBarImageScanner;
+
+#[test]
+fn scan_source() {
+    let mut scanner = ZBarImageScanner::new();
+
+    let size = 512;
+
+    let qrcode = common::qr_code(size);
+
+    // put the QR code on the right half of a frame which is twice as wide
+    let gray = GrayImage::from_fn(size as u32 * 2, size as u32, |x, y| {
+        let x = x as usize;
+
+        if x < size {
+            image::Luma([255])
+        } else {
+            image::Luma([qrcode[y as usize * size + x - size]])
+        }
+    });
+
+    let rgb = DynamicImage::ImageLuma8(gray.clone()).into_rgb8();
+
+    for results in [
+        scanner.scan_source(&gray).unwrap(),
+        scanner.scan_source(&rgb).unwrap(),
+        scanner.scan_source(&DynamicImage::ImageRgb8(rgb.clone())).unwrap(),
+        scanner
+            .scan_source(&DynamicImage::ImageLuma16(
+                DynamicImage::ImageLuma8(gray.clone()).into_luma16(),
+            ))
+            .unwrap(),
+    ] {
+        assert_eq!(1, results.len());
+        assert_eq!(common::URL.as_bytes(), results[0].data.as_slice());
+    }
+
+    let dynamic = DynamicImage::ImageRgb8(rgb.clone());
+
+    // sub-views keep the coordinates of the parent image
+    for results in [
+        scanner.scan_source(&gray.view(512, 0, 512, 512)).unwrap(),
+        scanner.scan_source(&rgb.view(512, 0, 512, 512)).unwrap(),
+        scanner.scan_source(&dynamic.view(512, 0, 512, 512)).unwrap(),
+    ] {
+        assert_eq!(1, results.len());
+        // left
+        assert_eq!(546, results[0].points.iter().map(|(x, _)| *x).min().unwrap());
+        // right
+        assert_eq!(991, results[0].points.iter().map(|(x, _)| *x).max().unwrap());
+    }
+
+    assert!(scanner.scan_source(&gray.view(0, 0, 512, 512)).unwrap().is_empty());
+}
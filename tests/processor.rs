@@ -0,0 +1,56 @@
+#![cfg(not(feature = "vendored"))]
+
+mod common;
+
+use std::time::Duration;
+
+use zbar_rust::{FourCC, ZBarConfig, ZBarImage, ZBarProcessor, ZBarRustError, ZBarSymbolType};
+
+#[test]
+fn process_image() {
+    let mut processor = ZBarProcessor::new(false);
+
+    let size = 512;
+
+    let data = common::qr_code(size);
+
+    let mut image = ZBarImage::from_owned(data, size as u32, size as u32, FourCC::Y800).unwrap();
+
+    processor.process_image(&mut image).unwrap();
+
+    let symbols = image.symbols().collect::<Vec<_>>();
+
+    assert_eq!(1, symbols.len());
+    assert_eq!(ZBarSymbolType::ZBarQRCode, symbols[0].symbol_type());
+    assert_eq!(common::URL.as_bytes(), symbols[0].data());
+
+    let results = processor.results().unwrap();
+
+    assert_eq!(1, results.len());
+    assert_eq!(common::URL.as_bytes(), results.iter().next().unwrap().data());
+
+    processor.set_config(ZBarSymbolType::ZBarQRCode, ZBarConfig::ZBarCfgEnable, 0).unwrap();
+
+    processor.process_image(&mut image).unwrap();
+
+    assert_eq!(0, image.symbols().count());
+
+    // an image without data is not passed to ZBar
+    let mut image = ZBarImage::new();
+
+    image.set_size(size as u32, size as u32).unwrap();
+    image.set_format(FourCC::Y800).unwrap();
+
+    assert_eq!(Err(ZBarRustError::NoImageData), processor.process_image(&mut image));
+}
+
+#[test]
+fn threaded_processor() {
+    let mut processor = ZBarProcessor::new(true);
+
+    // no window is open, so no key can be pressed
+    assert_eq!(Ok(None), processor.user_wait(Some(Duration::from_millis(10))));
+
+    // ZBar does not wait forever without a window
+    assert_eq!(Ok(None), processor.user_wait(None));
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use zbar_rust::{ZBarDecoder, ZBarScanner, ZBarSymbolType};
+
+#[test]
+fn scan_line() {
+    let row = common::row(&common::ean13_modules("9780306406157"), 4);
+
+    let mut scanner = ZBarScanner::with_decoder(ZBarDecoder::new());
+
+    let scan = scanner.scan_line(&row);
+
+    assert_eq!(1, scan.results.len());
+    assert_eq!(ZBarSymbolType::ZBarEAN13, scan.results[0].symbol_type);
+    assert_eq!(b"9780306406157", scan.results[0].data.as_slice());
+
+    // 30 bars, each with two edges
+    assert!(scan.edges.len() >= 60);
+    assert!(scan.edges.windows(2).all(|edges| edges[0].position < edges[1].position));
+    assert!(scan.edges.iter().all(|edge| edge.width > 0));
+
+    // scanning the same line as 32-bit samples gives the same result
+    let samples = row.iter().map(|sample| *sample as i32 * 16).collect::<Vec<i32>>();
+
+    let results = scanner.scan_line(&samples).results;
+
+    assert_eq!(1, results.len());
+    assert_eq!(scan.results[0].symbol_type, results[0].symbol_type);
+    assert_eq!(scan.results[0].data, results[0].data);
+
+    assert!(scanner.into_decoder().is_some());
+}
+
+#[test]
+fn scan_line_without_decoder() {
+    let row = common::row(&common::ean13_modules("9780306406157"), 4);
+
+    let mut scanner = ZBarScanner::new();
+
+    let scan = scanner.scan_line(&row);
+
+    assert!(scan.results.is_empty());
+    assert!(scan.edges.len() >= 60);
+    assert!(scanner.decoder().is_none());
+}
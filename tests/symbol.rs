@@ -0,0 +1,142 @@
+mod common;
+
+use zbar_rust::{ZBarConfig, ZBarImage, ZBarImageScanner, ZBarOrientation, ZBarSymbolType};
+
+#[test]
+fn symbol_properties() {
+    let mut scanner = ZBarImageScanner::new();
+
+    let size = 512;
+
+    let data = common::qr_code(size);
+
+    scanner.scan_y800(&data, size as u32, size as u32).unwrap();
+
+    let symbols = scanner.symbols().collect::<Vec<_>>();
+
+    assert_eq!(1, symbols.len());
+
+    let symbol = symbols[0];
+
+    assert_eq!(ZBarSymbolType::ZBarQRCode, symbol.symbol_type());
+    assert_eq!(common::URL.as_bytes(), symbol.data());
+    assert!(symbol.quality() > 0);
+    assert_eq!(ZBarOrientation::ZBarOrientUp, symbol.orientation());
+    assert!(symbol.modifiers().is_empty());
+    assert_eq!(4, symbol.points().len());
+}
+
+#[test]
+fn symbol_configs() {
+    let mut scanner = ZBarImageScanner::new();
+
+    let row = common::row(&common::ean13_modules("9780306406157"), 3);
+
+    let width = row.len();
+    let height = 120;
+
+    let data = row.repeat(height);
+
+    scanner.scan_y800(&data, width as u32, height as u32).unwrap();
+
+    let symbol = scanner.symbols().next().unwrap();
+
+    // EAN-13 is decoded with its check digit by default
+    assert_eq!(ZBarSymbolType::ZBarEAN13, symbol.symbol_type());
+    assert!(symbol.configs().contains(ZBarConfig::ZBarCfgEnable));
+    assert!(symbol.configs().contains(ZBarConfig::ZBarCfgEmitCheck));
+    assert!(!symbol.configs().contains(ZBarConfig::ZBarCfgASCII));
+
+    scanner.set_config(ZBarSymbolType::ZBarEAN13, ZBarConfig::ZBarCfgEmitCheck, 0).unwrap();
+    scanner.scan_y800(&data, width as u32, height as u32).unwrap();
+
+    let symbol = scanner.symbols().next().unwrap();
+
+    assert_eq!(b"978030640615", symbol.data());
+    assert!(symbol.configs().contains(ZBarConfig::ZBarCfgEnable));
+    assert!(!symbol.configs().contains(ZBarConfig::ZBarCfgEmitCheck));
+}
+
+#[test]
+fn composite_components() {
+    let mut scanner = ZBarImageScanner::new();
+
+    scanner.set_config(ZBarSymbolType::ZBarEAN5, ZBarConfig::ZBarCfgEnable, 1).unwrap();
+    scanner.set_config(ZBarSymbolType::ZBarComposite, ZBarConfig::ZBarCfgEnable, 1).unwrap();
+
+    // the add-on follows the symbol after a gap of 9 modules
+    let modules = format!(
+        "{}000000000{}",
+        common::ean13_modules("9780306406157"),
+        common::ean5_modules("51299")
+    );
+
+    let row = common::row(&modules, 3);
+
+    let width = row.len();
+    let height = 120;
+
+    let data = row.repeat(height);
+
+    let mut results = scanner.scan_y800(data, width as u32, height as u32).unwrap();
+
+    assert_eq!(1, results.len());
+
+    let result = results.remove(0);
+
+    assert_eq!(ZBarSymbolType::ZBarComposite, result.symbol_type);
+    assert_eq!(b"978030640615751299", result.data.as_slice());
+    assert_eq!(2, result.components.len());
+    assert_eq!(ZBarSymbolType::ZBarEAN13, result.components[0].symbol_type);
+    assert_eq!(b"9780306406157", result.components[0].data.as_slice());
+    assert_eq!(ZBarSymbolType::ZBarEAN5, result.components[1].symbol_type);
+    assert_eq!(b"51299", result.components[1].data.as_slice());
+    assert!(!result.components[1].points.is_empty());
+}
+
+#[test]
+fn symbol_set() {
+    let mut scanner = ZBarImageScanner::new();
+
+    assert!(scanner.results().is_none());
+
+    let size = 512;
+
+    let data = common::qr_code(size);
+
+    scanner.scan_y800(&data, size as u32, size as u32).unwrap();
+
+    let results = scanner.results().unwrap();
+
+    // the set outlives the next scan
+    scanner.scan_y800(vec![255u8; 64 * 64], 64, 64).unwrap();
+
+    assert_eq!(0, scanner.symbols().count());
+
+    assert_eq!(1, results.len());
+    assert_eq!(common::URL.as_bytes(), results.iter().next().unwrap().data());
+
+    let mut image = ZBarImage::new();
+
+    image.set_symbol_set(Some(&results.clone()));
+
+    let attached = image.symbol_set().unwrap();
+
+    assert_eq!(1, attached.len());
+    assert_eq!(common::URL.as_bytes(), (&attached).into_iter().next().unwrap().data());
+
+    image.set_symbol_set(None);
+
+    assert!(image.symbol_set().is_none());
+}
+
+#[test]
+fn symbol_type_raw_values() {
+    for symbol_type in ZBarSymbolType::VARIANTS.iter().copied() {
+        assert_eq!(symbol_type, ZBarSymbolType::from_raw(symbol_type.to_raw()));
+    }
+
+    assert_eq!(64, ZBarSymbolType::ZBarQRCode.to_raw());
+    assert_eq!(ZBarSymbolType::Unknown(3), ZBarSymbolType::from_raw(3));
+    assert_eq!(3, i32::from(ZBarSymbolType::Unknown(3)));
+}
@@ -0,0 +1,326 @@
+use std::{
+    env, fs,
+    mem::{align_of, offset_of, size_of},
+    path::PathBuf,
+};
+
+use enum_ordinalize::Ordinalize;
+use zbar_rust::{
+    sys::*, VideoControlType, ZBarColor, ZBarConfig, ZBarError, ZBarModifier, ZBarOrientation,
+    ZBarSymbolType,
+};
+
+#[test]
+fn constants() {
+    let symbol_types = [
+        (ZBarSymbolType::ZBarNone, ZBAR_NONE),
+        (ZBarSymbolType::ZBarPartial, ZBAR_PARTIAL),
+        (ZBarSymbolType::ZBarEAN2, ZBAR_EAN2),
+        (ZBarSymbolType::ZBarEAN5, ZBAR_EAN5),
+        (ZBarSymbolType::ZBarEAN8, ZBAR_EAN8),
+        (ZBarSymbolType::ZBarUPCE, ZBAR_UPCE),
+        (ZBarSymbolType::ZBarISBN10, ZBAR_ISBN10),
+        (ZBarSymbolType::ZBarUPCA, ZBAR_UPCA),
+        (ZBarSymbolType::ZBarEAN13, ZBAR_EAN13),
+        (ZBarSymbolType::ZBarISBN13, ZBAR_ISBN13),
+        (ZBarSymbolType::ZBarComposite, ZBAR_COMPOSITE),
+        (ZBarSymbolType::ZBarI25, ZBAR_I25),
+        (ZBarSymbolType::ZBarDataBar, ZBAR_DATABAR),
+        (ZBarSymbolType::ZBarDataBarExp, ZBAR_DATABAR_EXP),
+        (ZBarSymbolType::ZBarCodeBar, ZBAR_CODABAR),
+        (ZBarSymbolType::ZBarCode39, ZBAR_CODE39),
+        (ZBarSymbolType::ZBarPDF417, ZBAR_PDF417),
+        (ZBarSymbolType::ZBarQRCode, ZBAR_QRCODE),
+        #[cfg(zbar_0_23)]
+        (ZBarSymbolType::ZBarSQCode, ZBAR_SQCODE),
+        (ZBarSymbolType::ZBarCode93, ZBAR_CODE93),
+        (ZBarSymbolType::ZBarCode128, ZBAR_CODE128),
+        #[cfg(zbar_0_23)]
+        (ZBarSymbolType::ZBarCode32, ZBAR_CODE32),
+        (ZBarSymbolType::ZBarSymbol, ZBAR_SYMBOL),
+        (ZBarSymbolType::ZBarAddOn2, ZBAR_ADDON2),
+        (ZBarSymbolType::ZBarAddOn5, ZBAR_ADDON5),
+        (ZBarSymbolType::ZBarAddOn, ZBAR_ADDON),
+    ];
+
+    assert_eq!(ZBarSymbolType::VARIANTS.len(), symbol_types.len());
+
+    for (symbol_type, value) in symbol_types {
+        assert_eq!(value, symbol_type.to_raw());
+    }
+
+    assert_eq!(ZBAR_SPACE as isize, ZBarColor::ZBarSpace.ordinal());
+    assert_eq!(ZBAR_BAR as isize, ZBarColor::ZBarBar.ordinal());
+
+    assert_eq!(ZBAR_ORIENT_UNKNOWN as isize, ZBarOrientation::ZBarOrientUnknown.ordinal());
+    assert_eq!(ZBAR_ORIENT_UP as isize, ZBarOrientation::ZBarOrientUp.ordinal());
+    assert_eq!(ZBAR_ORIENT_RIGHT as isize, ZBarOrientation::ZBarOrientRight.ordinal());
+    assert_eq!(ZBAR_ORIENT_DOWN as isize, ZBarOrientation::ZBarOrientDown.ordinal());
+    assert_eq!(ZBAR_ORIENT_LEFT as isize, ZBarOrientation::ZBarOrientLeft.ordinal());
+
+    assert_eq!(ZBAR_OK as isize, ZBarError::ZBarOK.ordinal());
+    assert_eq!(ZBAR_ERR_BUSY as isize, ZBarError::ZBarErrBudy.ordinal());
+    assert_eq!(ZBAR_ERR_WINAPI as isize, ZBarError::ZBarErrWinAPI.ordinal());
+    assert_eq!(ZBAR_ERR_NUM as isize, ZBarError::ZBarErrNum.ordinal());
+
+    let configs = [
+        (ZBarConfig::ZBarCfgEnable, ZBAR_CFG_ENABLE),
+        (ZBarConfig::ZBarCfgAddCheck, ZBAR_CFG_ADD_CHECK),
+        (ZBarConfig::ZBarCfgEmitCheck, ZBAR_CFG_EMIT_CHECK),
+        (ZBarConfig::ZBarCfgASCII, ZBAR_CFG_ASCII),
+        #[cfg(zbar_0_23)]
+        (ZBarConfig::ZBarCfgBinary, ZBAR_CFG_BINARY),
+        (ZBarConfig::ZBarCfgNum, ZBAR_CFG_NUM),
+        (ZBarConfig::ZBarCfgMinLen, ZBAR_CFG_MIN_LEN),
+        (ZBarConfig::ZBarCfgMaxLen, ZBAR_CFG_MAX_LEN),
+        (ZBarConfig::ZBarCfgUncertainty, ZBAR_CFG_UNCERTAINTY),
+        (ZBarConfig::ZBarCfgPosition, ZBAR_CFG_POSITION),
+        #[cfg(zbar_0_23)]
+        (ZBarConfig::ZBarCfgTestInverted, ZBAR_CFG_TEST_INVERTED),
+        (ZBarConfig::ZBarCfgXDensity, ZBAR_CFG_X_DENSITY),
+        (ZBarConfig::ZBarCfgYDensity, ZBAR_CFG_Y_DENSITY),
+    ];
+
+    assert_eq!(ZBarConfig::VARIANTS.len(), configs.len());
+
+    for (config, value) in configs {
+        assert_eq!(value as isize, config.ordinal());
+    }
+
+    assert_eq!(ZBAR_MOD_GS1 as isize, ZBarModifier::ZBarModGS1.ordinal());
+    assert_eq!(ZBAR_MOD_AIM as isize, ZBarModifier::ZBarModAIM.ordinal());
+    assert_eq!(ZBAR_MOD_NUM as isize, ZBarModifier::ZBarModNum.ordinal());
+
+    assert_eq!(VIDEO_CNTL_INTEGER as isize, VideoControlType::VideoCntlInteger.ordinal());
+    assert_eq!(VIDEO_CNTL_BOOLEAN as isize, VideoControlType::VideoCntlBoolean.ordinal());
+}
+
+#[test]
+fn layouts() {
+    let pointer = size_of::<*const u8>();
+
+    assert_eq!(pointer, size_of::<Option<zbar_image_cleanup_handler_t>>());
+    assert_eq!(pointer, size_of::<Option<zbar_image_data_handler_t>>());
+    assert_eq!(pointer, size_of::<Option<zbar_decoder_handler_t>>());
+
+    assert_eq!(12, size_of::<video_resolution_t>());
+    assert_eq!(4, align_of::<video_resolution_t>());
+    assert_eq!(4, offset_of!(video_resolution_t, height));
+    assert_eq!(8, offset_of!(video_resolution_t, max_fps));
+
+    assert_eq!(pointer.max(align_of::<i64>()), offset_of!(video_control_menu_t, value));
+
+    assert_eq!(0, offset_of!(video_controls_t, name));
+    assert_eq!(pointer, offset_of!(video_controls_t, group));
+    assert_eq!(pointer * 2, offset_of!(video_controls_t, type_));
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn layouts_64() {
+    assert_eq!(16, size_of::<video_control_menu_t>());
+
+    assert_eq!(72, size_of::<video_controls_t>());
+    assert_eq!(8, align_of::<video_controls_t>());
+    assert_eq!(24, offset_of!(video_controls_t, min));
+    assert_eq!(32, offset_of!(video_controls_t, max));
+    assert_eq!(40, offset_of!(video_controls_t, def));
+    assert_eq!(48, offset_of!(video_controls_t, step));
+    assert_eq!(56, offset_of!(video_controls_t, menu_size));
+    assert_eq!(64, offset_of!(video_controls_t, menu));
+}
+
+/// Functions of `zbar.h` which are deliberately not bound, because they need D-Bus support.
+const NOT_BOUND: [&str; 2] = ["zbar_processor_request_dbus", "zbar_image_scanner_request_dbus"];
+
+/// Enums of `zbar.h`, which are passed as `c_int`.
+const ENUMS: [&str; 7] = [
+    "zbar_color_t",
+    "zbar_symbol_type_t",
+    "zbar_orientation_t",
+    "zbar_error_t",
+    "zbar_config_t",
+    "zbar_modifier_t",
+    "video_control_type_t",
+];
+
+/// The return type and the parameter types of a function, written the same way for C and Rust, like `c_int` or `zbar_image_t*`. Pointer constness is not compared because it does not change the ABI.
+type Signature = (String, Vec<String>);
+
+fn normalize(base: &str, pointers: usize) -> String {
+    let base = match base {
+        "int" | "signed" | "signed int" => "c_int".to_string(),
+        "unsigned" | "unsigned int" => "c_uint".to_string(),
+        "long" | "long int" => "c_long".to_string(),
+        "unsigned long" | "unsigned long int" => "c_ulong".to_string(),
+        "char" => "c_char".to_string(),
+        "float" => "c_float".to_string(),
+        "void" if pointers == 0 => "()".to_string(),
+        "void" => "c_void".to_string(),
+        base if ENUMS.contains(&base) => "c_int".to_string(),
+        // `struct video_controls_s` is `video_controls_t`
+        base => match base.strip_suffix("_s") {
+            Some(name) => format!("{name}_t"),
+            None => base.to_string(),
+        },
+    };
+
+    format!("{base}{}", "*".repeat(pointers))
+}
+
+fn c_type(declaration: &str) -> String {
+    let pointers = declaration.matches('*').count();
+
+    let words = declaration
+        .split(|c: char| c.is_whitespace() || c == '*')
+        .filter(|word| !word.is_empty() && !["const", "struct", "enum", "extern"].contains(word))
+        .collect::<Vec<&str>>();
+
+    normalize(&words.join(" "), pointers)
+}
+
+/// The type of a C parameter, without its name.
+fn c_parameter(parameter: &str) -> String {
+    const TYPE_WORDS: [&str; 8] =
+        ["void", "int", "long", "char", "float", "unsigned", "signed", "const"];
+
+    let parameter = parameter.trim();
+
+    let name_start = parameter
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .map_or(0, |index| index + 1);
+
+    let (ty, name) = parameter.split_at(name_start);
+
+    // a parameter which is only a type, like `void` or `zbar_image_t*`, has no name
+    if name.is_empty() || TYPE_WORDS.contains(&name) || ty.trim().is_empty() {
+        c_type(parameter)
+    } else {
+        c_type(ty)
+    }
+}
+
+/// Collect the `extern` function declarations of a C header.
+fn c_declarations(header: &str) -> Vec<(String, Signature)> {
+    let mut code = String::with_capacity(header.len());
+    let mut rest = header;
+
+    // drop the comments and the preprocessor lines
+    while let Some(index) = rest.find("/*") {
+        code.push_str(&rest[..index]);
+
+        rest = rest[index..].find("*/").map_or("", |end| &rest[index + end + 2..]);
+    }
+
+    code.push_str(rest);
+
+    let code = code
+        .lines()
+        .map(|line| line.split("//").next().unwrap())
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    code.split([';', '{', '}'])
+        .map(str::trim)
+        .filter(|statement| statement.starts_with("extern ") && statement.contains('('))
+        .map(|statement| {
+            let open = statement.find('(').unwrap();
+            let close = statement.rfind(')').unwrap();
+
+            let prefix = statement[..open].trim();
+            let name_start =
+                prefix.rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap() + 1;
+
+            let parameters = statement[open + 1..close].trim();
+
+            let parameters = if parameters == "void" || parameters.is_empty() {
+                Vec::new()
+            } else {
+                parameters.split(',').map(c_parameter).collect()
+            };
+
+            (prefix[name_start..].to_string(), (c_type(&prefix[..name_start]), parameters))
+        })
+        .collect()
+}
+
+fn rust_type(ty: &str) -> String {
+    let mut ty = ty.trim();
+    let mut pointers = 0;
+
+    loop {
+        if let Some(pointee) = ty.strip_prefix("*const ").or_else(|| ty.strip_prefix("*mut ")) {
+            ty = pointee.trim();
+        } else if let Some(function) = ty.strip_prefix("Option<") {
+            // a nullable pointer to a handler
+            ty = function.strip_suffix('>').unwrap().trim();
+        } else {
+            break;
+        }
+
+        pointers += 1;
+    }
+
+    match ty {
+        "c_void" if pointers == 0 => "()".to_string(),
+        ty if ENUMS.contains(&ty) => normalize("int", pointers),
+        ty => format!("{ty}{}", "*".repeat(pointers)),
+    }
+}
+
+/// Find the declaration of a function in the `zbar_extern!` blocks of the `sys` module.
+fn rust_declaration(sys: &str, name: &str) -> Option<Signature> {
+    let start = sys.find(&format!("pub fn {name}("))? + "pub fn (".len() + name.len();
+    let end = start + sys[start..].find(';')?;
+
+    let declaration = &sys[start..end];
+    let close = declaration.rfind(')')?;
+
+    let parameters = declaration[..close]
+        .split(',')
+        .map(str::trim)
+        .filter(|parameter| !parameter.is_empty())
+        .map(|parameter| rust_type(parameter.split_once(':').unwrap().1))
+        .collect();
+
+    let ret = match declaration[close + 1..].trim().strip_prefix("->") {
+        Some(ret) => rust_type(ret),
+        None => "()".to_string(),
+    };
+
+    Some((ret, parameters))
+}
+
+#[test]
+fn header_coverage() {
+    let include_dirs = env::var("ZBAR_INCLUDE_DIRS").unwrap_or_else(|_| "/usr/include".into());
+
+    let header = include_dirs
+        .split(':')
+        .map(|d| PathBuf::from(d).join("zbar.h"))
+        .find_map(|path| fs::read_to_string(path).ok())
+        .unwrap_or_else(|| {
+            panic!(
+                "zbar.h is not found in {include_dirs}. Set ZBAR_INCLUDE_DIRS to the folder which \
+                 has it."
+            )
+        });
+
+    let sys = include_str!("../src/sys.rs");
+
+    let declarations = c_declarations(&header);
+
+    assert!(!declarations.is_empty(), "no functions are found in zbar.h");
+
+    for (name, signature) in declarations {
+        if NOT_BOUND.contains(&name.as_str()) {
+            continue;
+        }
+
+        let bound = rust_declaration(sys, &name)
+            .unwrap_or_else(|| panic!("`{name}` of zbar.h is not bound"));
+
+        assert_eq!(signature, bound, "the signature of `{name}` differs from zbar.h");
+    }
+}